@@ -1,95 +1,365 @@
 #![allow(clippy::tabs_in_doc_comments)]
 
+use crate::client_model::{AllocResourceUsage, HostStats, NodeMetaApplyRequest, NodeMetaResponse};
+use crate::error::NomadError;
 use crate::jobs::{
-    Allocation, CreateJobRequest, CreateJobResponse, DispatchJobRequest, DispatchJobResponse, Job,
-    JobStopResponse, ListJobAllocationsResponse, ParseJobPayload,
+    AllocLifecycleResponse, AllocRestartRequest, AllocSignalRequest, AllocStopResponse, Allocation,
+    AllocationListOptions, AllocationListStub, CreateJobRequest, CreateJobResponse,
+    DispatchJobRequest, DispatchJobResponse, DispatchViolation, EvalOptions, Job,
+    JobEvaluateRequest, JobPlanRequest, JobPlanResponse, JobRegisterOptions, JobRevertRequest,
+    JobScaleStatus, JobStabilityRequest, JobStabilityResponse, JobStopResponse, JobSummary,
+    JobValidateRequest, JobValidateResponse, JobVersionsResponse, ParseJobPayload,
+    PeriodicForceResponse, ScalingRequest, DISPATCH_PAYLOAD_LIMIT,
 };
-use log::{debug, info, trace};
-use reqwest::Client;
+use crate::nodes::{
+    DrainSpec, Node, NodeListStub, NodeUpdateDrainRequest, NodeUpdateEligibilityRequest,
+    NodeUpdateResponse,
+};
+use crate::query::{QueryMeta, QueryOptions};
+use crate::tls::{env, TlsConfig};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use log::{debug, trace};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::env::var;
-use std::fs::File;
-use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const DEFAULT_ADDRESS: &str = "http://127.0.0.1:4646";
 
 #[derive(Clone)]
 pub struct NomadClient {
     http_client: Client,
     base_url: String,
     authorization_token: String,
+    region: Option<String>,
+    namespace: Option<String>,
+    tls: Option<Arc<rustls::ClientConfig>>,
 }
 
-impl NomadClient {
-    pub fn new(base_url: String, authorization_token: String) -> Self {
-        let http_client = if let Ok(cert) = var("NOMAD_CACERT") {
-            let mut buf = Vec::new();
-            File::open(cert).unwrap().read_to_end(&mut buf).unwrap();
-            let cert = reqwest::Certificate::from_pem(&buf).unwrap();
-            Client::builder().add_root_certificate(cert).danger_accept_invalid_certs(true).build().unwrap()
+/// Builder for [`NomadClient`].
+///
+/// ```no_run
+/// use nomad_api::client::NomadClient;
+///
+/// let client = NomadClient::builder()
+///     .address("https://nomad.service.consul:4646")
+///     .namespace("numpire")
+///     .ca_cert("/etc/nomad.d/ca.pem")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NomadClientBuilder {
+    address: String,
+    token: String,
+    region: Option<String>,
+    namespace: Option<String>,
+    tls: TlsConfig,
+}
+
+impl Default for NomadClientBuilder {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS.to_string(),
+            token: String::new(),
+            region: None,
+            namespace: None,
+            tls: TlsConfig::default(),
+        }
+    }
+}
+
+impl NomadClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from the environment the Nomad CLI reads:
+    ///
+    /// `NOMAD_ADDR`, `NOMAD_TOKEN`, `NOMAD_REGION`, `NOMAD_NAMESPACE`, `NOMAD_CACERT`,
+    /// `NOMAD_CAPATH`, `NOMAD_CLIENT_CERT`, `NOMAD_CLIENT_KEY`, `NOMAD_TLS_SERVER_NAME` and
+    /// `NOMAD_SKIP_VERIFY`.
+    ///
+    /// Like the CLI, empty variables are ignored, the address defaults to
+    /// `http://127.0.0.1:4646` and a `NOMAD_SKIP_VERIFY` that is not a boolean is ignored.
+    pub fn from_env() -> Self {
+        let mut builder = Self::default();
+        if let Some(address) = env("NOMAD_ADDR") {
+            builder.address = address;
+        }
+        if let Some(token) = env("NOMAD_TOKEN") {
+            builder.token = token;
+        }
+        builder.region = env("NOMAD_REGION");
+        builder.namespace = env("NOMAD_NAMESPACE");
+        builder.tls = TlsConfig::from_env();
+        builder
+    }
+
+    /// Address of the Nomad agent, e.g. `https://127.0.0.1:4646`.
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// ACL token sent as `X-Nomad-Token`.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = token.into();
+        self
+    }
+
+    /// Region applied to every request.
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Namespace applied to every request.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// PEM file with the CA certificate used to verify the server.
+    pub fn ca_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.ca_cert = Some(path.into());
+        self
+    }
+
+    /// PEM encoded CA certificate used to verify the server.
+    pub fn ca_cert_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.tls.ca_cert_pem = Some(pem.into());
+        self
+    }
+
+    /// Directory of PEM encoded CA certificates used to verify the server.
+    pub fn ca_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.ca_path = Some(path.into());
+        self
+    }
+
+    /// PEM file with the client certificate used for mutual TLS.
+    pub fn client_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.client_cert = Some(path.into());
+        self
+    }
+
+    /// PEM file with the private key of the client certificate.
+    pub fn client_key(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.client_key = Some(path.into());
+        self
+    }
+
+    /// PEM encoded client certificate and private key used for mutual TLS.
+    pub fn client_identity_pem(
+        mut self,
+        cert: impl Into<Vec<u8>>,
+        key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.tls.client_cert_pem = Some(cert.into());
+        self.tls.client_key_pem = Some(key.into());
+        self
+    }
+
+    /// Name used to verify the server certificate instead of the host of the address.
+    pub fn tls_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.tls.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Disables verification of the server certificate. Only use this against test clusters.
+    pub fn skip_verify(mut self, skip_verify: bool) -> Self {
+        self.tls.insecure = skip_verify;
+        self
+    }
+
+    /// Replaces all TLS settings at once.
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn build(self) -> Result<NomadClient, NomadError> {
+        let mut builder = Client::builder();
+        let tls = if self.tls.is_configured() {
+            let config = self.tls.client_config()?;
+            builder = builder.use_preconfigured_tls(config.clone());
+            Some(Arc::new(config))
         } else {
-            Client::new()
+            None
         };
+        let http_client = builder
+            .build()
+            .map_err(|e| NomadError::Config(format!("cannot build HTTP client: {}", e)))?;
 
-        Self {
+        Ok(NomadClient {
             http_client,
-            base_url,
-            authorization_token,
-        }
+            base_url: self.address.trim_end_matches('/').to_string(),
+            authorization_token: self.token,
+            region: self.region,
+            namespace: self.namespace,
+            tls,
+        })
+    }
+}
+
+impl NomadClient {
+    /// Creates a client for `base_url`, taking the TLS settings from the environment (see
+    /// [`TlsConfig::from_env`]).
+    pub fn new(base_url: String, authorization_token: String) -> Result<Self, NomadError> {
+        NomadClientBuilder::new()
+            .address(base_url)
+            .token(authorization_token)
+            .tls(TlsConfig::from_env())
+            .build()
+    }
+
+    pub fn builder() -> NomadClientBuilder {
+        NomadClientBuilder::new()
+    }
+
+    /// Builds a client from the environment variables understood by the Nomad CLI, see
+    /// [`NomadClientBuilder::from_env`].
+    pub fn from_env() -> Result<Self, NomadError> {
+        NomadClientBuilder::from_env().build()
     }
 
     /// https://www.nomadproject.io/api-docs/jobs#list-jobs
     /// This endpoint lists all known jobs in the system registered with Nomad.
     /// Method      Path
     /// GET         /v1/jobs
-    pub async fn list_jobs(&self) -> Result<Vec<Job>, reqwest::Error> {
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:list-jobs
+    pub async fn list_jobs(&self, q: &QueryOptions) -> Result<(Vec<Job>, QueryMeta), NomadError> {
         let url = format!("{}/v1/jobs", &self.base_url);
         trace!("ListJobs call to {}", &url);
-        let response = self
-            .http_client
-            .get(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .send()
-            .await?
-            .json::<Vec<Job>>()
-            .await?;
-        Ok(response)
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#read-job
+    /// This endpoint reads information about a single job for its specification and status.
+    /// Method	Path	Produces
+    /// GET	/v1/job/:job_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    pub async fn read_job(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(Job, QueryMeta), NomadError> {
+        let url = format!("{}/v1/job/{}", &self.base_url, id);
+        trace!("ReadJob call to {}", &url);
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#read-job-summary
+    /// This endpoint reads summary information about a job.
+    /// Method	Path	Produces
+    /// GET	/v1/job/:job_id/summary	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    ///
+    /// For a parameterized job, `children` counts its dispatched jobs, e.g. `pending` is the
+    /// backlog of dispatches not placed yet.
+    pub async fn read_job_summary(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(JobSummary, QueryMeta), NomadError> {
+        let url = format!("{}/v1/job/{}/summary", &self.base_url, id);
+        trace!("ReadJobSummary call to {}", &url);
+        self.send_query(self.query(&url, q)).await
     }
 
     /// https://www.nomadproject.io/api-docs/jobs#stop-a-job
     /// This endpoint deregisters a job, and stops all allocations part of it.
     /// Method    Path            Produces
     /// DELETE    /v1/job/:job_id    application/json
-    pub async fn stop_job(&self, job_id: &str) -> Result<JobStopResponse, reqwest::Error> {
+    pub async fn stop_job(&self, job_id: &str) -> Result<JobStopResponse, NomadError> {
         let url = format!("{}/v1/job/{}", &self.base_url, job_id);
-        trace!("Stop job {} call to {}", &url, job_id);
-        let response = self
-            .http_client
-            .delete(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .send()
-            .await?
-            .json::<JobStopResponse>()
-            .await?;
-        Ok(response)
+        trace!("Stop job {} call to {}", job_id, &url);
+        self.send(self.request(Method::DELETE, &url)).await
     }
 
     /// https://www.nomadproject.io/api-docs/jobs#list-job-allocations
     /// Method	Path                        Produces
     /// GET    /v1/job/:job_id/allocations    application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
     pub async fn list_job_allocations(
         &self,
         id: &str,
-    ) -> Result<Vec<ListJobAllocationsResponse>, reqwest::Error> {
+        q: &QueryOptions,
+    ) -> Result<(Vec<AllocationListStub>, QueryMeta), NomadError> {
         let url = format!("{}/v1/job/{}/allocations", &self.base_url, id);
         trace!("ListJobAllocations call to {}", &url);
-        let response = self
-            .http_client
-            .get(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .send()
-            .await?
-            .json::<Vec<ListJobAllocationsResponse>>()
-            .await?;
-        Ok(response)
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/allocations#list-allocations
+    /// This endpoint lists all allocations.
+    /// Method	Path	Produces
+    /// GET	/v1/allocations	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    ///
+    /// Every page is read, following `X-Nomad-NextToken` from the page size set by
+    /// `q.per_page`; the returned [`QueryMeta`] is the one of the last page. Set the namespace
+    /// of `q` to `*` to list all namespaces.
+    pub async fn list_allocations(
+        &self,
+        options: &AllocationListOptions,
+        q: &QueryOptions,
+    ) -> Result<(Vec<AllocationListStub>, QueryMeta), NomadError> {
+        let url = format!("{}/v1/allocations", &self.base_url);
+        trace!("ListAllocations call to {}", &url);
+        let mut q = q.clone();
+        if options.resources {
+            q.params.insert("resources".to_string(), "true".to_string());
+        }
+        if !options.task_states {
+            q.params
+                .insert("task_states".to_string(), "false".to_string());
+        }
+        let mut allocations = Vec::new();
+        loop {
+            let (page, meta): (Vec<AllocationListStub>, QueryMeta) =
+                self.send_query(self.query(&url, &q)).await?;
+            allocations.extend(page);
+            match &meta.next_token {
+                Some(next_token) => {
+                    // Only the first page blocks, the following ones are read right away.
+                    q.index = None;
+                    q.next_token = Some(next_token.clone());
+                }
+                None => return Ok((allocations, meta)),
+            }
+        }
+    }
+
+    /// https://www.nomadproject.io/api-docs/evaluations#list-allocations-for-evaluation
+    /// This endpoint lists the allocations created or modified for the given evaluation.
+    /// Method	Path	Produces
+    /// GET	/v1/evaluation/:eval_id/allocations	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    pub async fn list_evaluation_allocations(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(Vec<AllocationListStub>, QueryMeta), NomadError> {
+        let url = format!("{}/v1/evaluation/{}/allocations", &self.base_url, id);
+        trace!("ListEvaluationAllocations call to {}", &url);
+        self.send_query(self.query(&url, q)).await
     }
 
     /// https://www.nomadproject.io/api-docs/jobs#read-allocation
@@ -99,18 +369,93 @@ impl NomadClient {
     /// Parameters:
     /// :alloc_id (string: <required>)- Specifies the UUID of the allocation. This must be the full
     /// UUID, not the short 8-character one. This is specified as part of the path.
-    pub async fn read_allocation(&self, id: &str) -> Result<Allocation, reqwest::Error> {
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    pub async fn read_allocation(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(Allocation, QueryMeta), NomadError> {
         let url = format!("{}/v1/allocation/{}", &self.base_url, id);
         trace!("ReadAllocation call to {}", &url);
-        let response = self
-            .http_client
-            .get(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .send()
-            .await?;
-        // info!("ReadAllocation: {:?}", &response);
-        let response = response.json::<Allocation>().await?;
-        Ok(response)
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/allocations#stop-allocation
+    /// This endpoint stops and reschedules a specific allocation.
+    /// Method	Path	Produces
+    /// POST	/v1/allocation/:alloc_id/stop	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:alloc-lifecycle
+    ///
+    /// `no_shutdown_delay` skips the `shutdown_delay` of the group and its tasks. The returned
+    /// evaluation places the replacement allocation.
+    pub async fn stop_allocation(
+        &self,
+        id: &str,
+        no_shutdown_delay: bool,
+    ) -> Result<AllocStopResponse, NomadError> {
+        let url = format!("{}/v1/allocation/{}/stop", &self.base_url, id);
+        trace!("StopAllocation call to {}", &url);
+        let mut request = self.request(Method::POST, &url);
+        if no_shutdown_delay {
+            request = request.query(&[("no_shutdown_delay", "true")]);
+        }
+        self.send(request).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/allocations#restart-allocation
+    /// This endpoint restarts an allocation or task in-place.
+    /// Method	Path	Produces
+    /// POST	/v1/client/allocation/:alloc_id/restart	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:alloc-lifecycle
+    ///
+    /// Restarts `task`, or every running task when `None`. Lifecycle tasks that already
+    /// completed, e.g. prestart tasks, are not run again. The restart happens in place on the
+    /// client, Nomad does not create an evaluation for it.
+    pub async fn restart_allocation(
+        &self,
+        id: &str,
+        task: Option<&str>,
+    ) -> Result<AllocLifecycleResponse, NomadError> {
+        let url = format!("{}/v1/client/allocation/{}/restart", &self.base_url, id);
+        trace!("RestartAllocation call to {}", &url);
+        let request = AllocRestartRequest {
+            task_name: task.unwrap_or_default().to_string(),
+            all_tasks: false,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/allocations#signal-allocation
+    /// This endpoint sends a signal to an allocation or task.
+    /// Method	Path	Produces
+    /// POST	/v1/client/allocation/:alloc_id/signal	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:alloc-lifecycle
+    ///
+    /// `signal` is a signal name such as `SIGUSR1`, sent to `task` or to every task when
+    /// `None`. Like restarts, signals are delivered by the client without an evaluation.
+    pub async fn signal_allocation(
+        &self,
+        id: &str,
+        signal: &str,
+        task: Option<&str>,
+    ) -> Result<AllocLifecycleResponse, NomadError> {
+        let url = format!("{}/v1/client/allocation/{}/signal", &self.base_url, id);
+        trace!("SignalAllocation call to {}", &url);
+        let request = AllocSignalRequest {
+            signal: signal.to_string(),
+            task: task.unwrap_or_default().to_string(),
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
     }
 
     /// https://www.nomadproject.io/api-docs/jobs#dispatch-job
@@ -128,27 +473,89 @@ impl NomadClient {
         job: &str,
         payload: Option<String>,
         meta: HashMap<String, String>,
-    ) -> Result<DispatchJobResponse, reqwest::Error> {
+    ) -> Result<DispatchJobResponse, NomadError> {
         let url = format!("{}/v1/job/{}/dispatch", &self.base_url, job);
         trace!("Dispatch job call to {}", &url);
         let request = DispatchJobRequest {
             payload: payload.unwrap_or_default(),
             meta,
         };
-        let test = serde_json::to_string(&request).unwrap();
-        info!("{}", test);
-        // println!("{}", test);
-        let response = self
-            .http_client
-            .post(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .json(&request)
-            .send()
-            .await?;
-        // println!("{:?}", &response);
-        let response = response.json::<DispatchJobResponse>().await?;
+        debug!("Dispatch request: {:?}", &request);
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
 
-        Ok(response)
+    /// https://www.nomadproject.io/api-docs/jobs#dispatch-job
+    /// This endpoint dispatches a new instance of a parameterized job.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/dispatch	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:dispatch-job
+    ///
+    /// Unlike [`NomadClient::dispatch_job`] the payload is passed raw and base64 encoded here,
+    /// payloads above [`DISPATCH_PAYLOAD_LIMIT`] fail with [`NomadError::InvalidRequest`] without
+    /// reaching Nomad. `meta` is any value serializing to a flat map, its numbers and booleans
+    /// are converted to strings, nested values to their JSON text and `None`s are left out.
+    ///
+    /// Dispatching again with the same `idempotency_token` returns the job dispatched first
+    /// instead of starting another one, as long as that job still exists.
+    pub async fn dispatch_job_raw<M: Serialize + ?Sized>(
+        &self,
+        job: &str,
+        payload: Option<&[u8]>,
+        meta: &M,
+        idempotency_token: Option<&str>,
+    ) -> Result<DispatchJobResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/dispatch", &self.base_url, job);
+        trace!("Dispatch job call to {}", &url);
+        let payload = payload.unwrap_or_default();
+        if payload.len() > DISPATCH_PAYLOAD_LIMIT {
+            return Err(NomadError::InvalidRequest(format!(
+                "dispatch payload is {} bytes, Nomad accepts at most {}",
+                payload.len(),
+                DISPATCH_PAYLOAD_LIMIT
+            )));
+        }
+        let request = DispatchJobRequest {
+            payload: STANDARD.encode(payload),
+            meta: dispatch_meta(meta)?,
+        };
+        debug!("Dispatch request: {:?}", &request);
+        let mut builder = self.request(Method::POST, &url).json(&request);
+        if let Some(token) = idempotency_token {
+            builder = builder.query(&[("idempotency_token", token)]);
+        }
+        self.send(builder).await
+    }
+
+    /// Dispatches a parameterized job like [`NomadClient::dispatch_job_raw`] after checking
+    /// `payload` and `meta` against the `parameterized` block of the job.
+    ///
+    /// The job is read first, a dispatch Nomad would reject fails with
+    /// [`NomadError::InvalidDispatch`] listing every problem, e.g. a misspelled meta key, without
+    /// being sent.
+    pub async fn dispatch_job_checked<M: Serialize + ?Sized>(
+        &self,
+        job: &str,
+        payload: Option<&[u8]>,
+        meta: &M,
+        idempotency_token: Option<&str>,
+    ) -> Result<DispatchJobResponse, NomadError> {
+        let (parent, _) = self.read_job(job, &QueryOptions::default()).await?;
+        let flat = dispatch_meta(meta)?;
+        let violations = match parent.parameterized_job {
+            Some(config) => config.check(payload, &flat),
+            None => vec![DispatchViolation::NotParameterized],
+        };
+        if !violations.is_empty() {
+            return Err(NomadError::InvalidDispatch {
+                job: job.to_string(),
+                violations,
+            });
+        }
+        self.dispatch_job_raw(job, payload, &flat, idempotency_token)
+            .await
     }
 
     /// Parse Job
@@ -162,28 +569,17 @@ impl NomadClient {
     /// NO	none
     /// »Parameters
     ///
-    ///     JobHCL (string: <required>) - Specifies the HCL definition of the job encoded in a JSON string.
-    ///     Canonicalize (bool: false) - Flag to enable setting any unset fields to their default values.
-    pub async fn parse_job(&self, hcl: &str, canonicalize: bool) -> Result<Job, reqwest::Error> {
+    /// JobHCL (string: <required>) - Specifies the HCL definition of the job encoded in a JSON string.
+    /// Canonicalize (bool: false) - Flag to enable setting any unset fields to their default values.
+    pub async fn parse_job(&self, hcl: &str, canonicalize: bool) -> Result<Job, NomadError> {
         let url = format!("{}/v1/jobs/parse", &self.base_url);
         trace!("Parse job call to {}", &url);
         let request = ParseJobPayload {
             job_hcl: hcl.to_string(),
             canonicalize,
         };
-        // let test = serde_json::to_string(&request).unwrap();
-        // println!("{}", test);
-        let response = self
-            .http_client
-            .post(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .json(&request)
-            .send()
-            .await?;
-        debug!("Response: {:?}", &response);
-        let response = response.json::<Job>().await?;
-
-        Ok(response)
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
     }
 
     /// Create Job
@@ -198,33 +594,610 @@ impl NomadClient {
     /// namespace:sentinel-override if PolicyOverride set
     /// »Parameters
     ///
-    ///     Job (Job: <required>) - Specifies the JSON definition of the job.
+    /// Job (Job: <required>) - Specifies the JSON definition of the job.
+    ///
+    /// EnforceIndex (bool: false) - If set, the job will only be registered if the passed JobModifyIndex matches the current job's index. If the index is zero, the register only occurs if the job is new. This paradigm allows check-and-set style job updating.
+    ///
+    /// JobModifyIndex (int: 0) - Specifies the JobModifyIndex to enforce the current job is at.
     ///
-    ///     EnforceIndex (bool: false) - If set, the job will only be registered if the passed JobModifyIndex matches the current job's index. If the index is zero, the register only occurs if the job is new. This paradigm allows check-and-set style job updating.
+    /// PolicyOverride (bool: false) - If set, any soft mandatory Sentinel policies will be overridden. This allows a job to be registered when it would be denied by policy.
     ///
-    ///     JobModifyIndex (int: 0) - Specifies the JobModifyIndex to enforce the current job is at.
+    /// PreserveCounts (bool: false) - If set, existing task group counts are preserved, over those specified in the new job spec.
+    pub async fn create_job(&self, job: &Job) -> Result<CreateJobResponse, NomadError> {
+        self.register_job(job, &JobRegisterOptions::default()).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#create-job
+    /// Method	Path	Produces
+    /// POST	/v1/jobs	application/json
     ///
-    ///     PolicyOverride (bool: false) - If set, any soft mandatory Sentinel policies will be overridden. This allows a job to be registered when it would be denied by policy.
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    /// namespace:sentinel-override if PolicyOverride set
     ///
-    ///     PreserveCounts (bool: false) - If set, existing task group counts are preserved, over those specified in the new job spec.
-    pub async fn create_job(&self, job: &Job) -> Result<CreateJobResponse, reqwest::Error> {
+    /// Registers `job` with the given `options`. When `enforce_index` is set and the job was
+    /// modified concurrently, Nomad rejects the registration with a
+    /// [`NomadError::Http`] whose body mentions the conflicting job modify index; read the job
+    /// again before retrying.
+    pub async fn register_job(
+        &self,
+        job: &Job,
+        options: &JobRegisterOptions,
+    ) -> Result<CreateJobResponse, NomadError> {
         let url = format!("{}/v1/jobs", &self.base_url);
-        trace!("Parse job call to {}", &url);
-        let request = CreateJobRequest { job: job.clone() };
+        trace!("Create job call to {}", &url);
+        let request = CreateJobRequest::new(job.clone(), options);
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
 
-        let test = serde_json::to_string(&request).unwrap();
-        println!("{}", test);
-        let response = self
-            .http_client
-            .post(&url)
-            .header("X-Nomad-Token", &self.authorization_token)
-            .json(&request)
-            .send()
+    /// https://www.nomadproject.io/api-docs/jobs#update-existing-job
+    /// This endpoint registers a new job or updates an existing job.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    /// namespace:sentinel-override if PolicyOverride set
+    ///
+    /// Same as [`NomadClient::register_job`] on the path of the job, see
+    /// [`JobRegisterOptions::check_index`] for check-and-set updates.
+    pub async fn update_job(
+        &self,
+        job: &Job,
+        options: &JobRegisterOptions,
+    ) -> Result<CreateJobResponse, NomadError> {
+        let url = format!("{}/v1/job/{}", &self.base_url, job.id);
+        trace!("UpdateJob call to {}", &url);
+        let request = CreateJobRequest::new(job.clone(), options);
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#create-job-plan
+    /// This endpoint invokes a dry-run of the scheduler for the job.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/plan	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    /// namespace:sentinel-override if PolicyOverride set
+    ///
+    /// With `diff` the response carries the differences between `job` and the registered job,
+    /// print the response to get them in the format of `nomad job plan`.
+    pub async fn plan_job(&self, job: &Job, diff: bool) -> Result<JobPlanResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/plan", &self.base_url, job.id);
+        trace!("PlanJob call to {}", &url);
+        let request = JobPlanRequest {
+            job: job.clone(),
+            diff,
+            policy_override: false,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#list-job-versions
+    /// This endpoint reads information about all versions of a job.
+    /// Method	Path	Produces
+    /// GET	/v1/job/:job_id/versions	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job
+    pub async fn list_job_versions(
+        &self,
+        id: &str,
+        diffs: bool,
+        q: &QueryOptions,
+    ) -> Result<(JobVersionsResponse, QueryMeta), NomadError> {
+        let url = format!("{}/v1/job/{}/versions", &self.base_url, id);
+        trace!("ListJobVersions call to {}", &url);
+        self.send_query(self.query(&url, q).query(&[("diffs", diffs)]))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#revert-to-older-job-version
+    /// This endpoint reverts the job to an older version.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/revert	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    ///
+    /// With `enforce_prior_version` the revert only happens if the current version of the job
+    /// is still that version.
+    pub async fn revert_job(
+        &self,
+        id: &str,
+        version: u64,
+        enforce_prior_version: Option<u64>,
+    ) -> Result<CreateJobResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/revert", &self.base_url, id);
+        trace!("RevertJob call to {}", &url);
+        let request = JobRevertRequest {
+            job_id: id.to_string(),
+            job_version: version,
+            enforce_prior_version,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#set-job-stability
+    /// This endpoint sets the job's stability.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/stable	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    pub async fn set_job_stability(
+        &self,
+        id: &str,
+        version: u64,
+        stable: bool,
+    ) -> Result<JobStabilityResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/stable", &self.base_url, id);
+        trace!("SetJobStability call to {}", &url);
+        let request = JobStabilityRequest {
+            job_id: id.to_string(),
+            job_version: version,
+            stable,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#create-job-evaluation
+    /// This endpoint creates a new evaluation for the given job. This can be used to force
+    /// run the scheduling logic if necessary.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/evaluate	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-job
+    pub async fn evaluate_job(
+        &self,
+        id: &str,
+        force_reschedule: bool,
+    ) -> Result<CreateJobResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/evaluate", &self.base_url, id);
+        trace!("EvaluateJob call to {}", &url);
+        let request = JobEvaluateRequest {
+            job_id: id.to_string(),
+            eval_options: EvalOptions { force_reschedule },
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#force-new-periodic-instance
+    /// This endpoint forces a new instance of the periodic job. A new instance will be created
+    /// even if it violates the job's prohibit_overlap settings.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/periodic/force	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:dispatch-job or namespace:submit-job
+    pub async fn force_periodic(&self, id: &str) -> Result<PeriodicForceResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/periodic/force", &self.base_url, id);
+        trace!("ForcePeriodic call to {}", &url);
+        self.send(self.request(Method::POST, &url)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/validate#validate-job
+    /// This endpoint validates a Nomad job file.
+    /// Method	Path	Produces
+    /// POST	/v1/validate/job	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-job
+    ///
+    /// An invalid job is not an error: check [`JobValidateResponse::is_valid`] and the
+    /// validation errors it carries.
+    pub async fn validate_job(&self, job: &Job) -> Result<JobValidateResponse, NomadError> {
+        let url = format!("{}/v1/validate/job", &self.base_url);
+        trace!("ValidateJob call to {}", &url);
+        let request = JobValidateRequest { job: job.clone() };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#scale-task-group
+    /// This endpoint performs a scaling action against a job.
+    /// Method	Path	Produces
+    /// POST	/v1/job/:job_id/scale	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:scale-job or namespace:submit-job
+    /// namespace:sentinel-override if PolicyOverride set
+    ///
+    /// Only the count of `group` changes, the rest of the registered job is left as is. A
+    /// `None` count records a scaling event with `message` and `meta` without scaling.
+    pub async fn scale_task_group(
+        &self,
+        job: &str,
+        group: &str,
+        count: Option<i64>,
+        message: &str,
+        meta: Option<HashMap<String, serde_json::Value>>,
+        policy_override: bool,
+    ) -> Result<CreateJobResponse, NomadError> {
+        let url = format!("{}/v1/job/{}/scale", &self.base_url, job);
+        trace!("ScaleTaskGroup call to {}", &url);
+        let mut target = HashMap::new();
+        target.insert("Group".to_string(), group.to_string());
+        let request = ScalingRequest {
+            count,
+            target,
+            message: message.to_string(),
+            error: false,
+            meta,
+            policy_override,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/jobs#read-job-scale-status
+    /// This endpoint reads scale information about a job.
+    /// Method	Path	Produces
+    /// GET	/v1/job/:job_id/scale	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	namespace:read-job-scaling or namespace:read-job
+    pub async fn read_job_scale_status(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(JobScaleStatus, QueryMeta), NomadError> {
+        let url = format!("{}/v1/job/{}/scale", &self.base_url, id);
+        trace!("ReadJobScaleStatus call to {}", &url);
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#list-nodes
+    /// This endpoint lists all nodes registered with Nomad.
+    /// Method	Path	Produces
+    /// GET	/v1/nodes	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	node:read
+    ///
+    /// Set the `resources` parameter of `q` to `true` to include the node resources, e.g. to
+    /// find nodes with GPUs.
+    pub async fn list_nodes(
+        &self,
+        q: &QueryOptions,
+    ) -> Result<(Vec<NodeListStub>, QueryMeta), NomadError> {
+        let url = format!("{}/v1/nodes", &self.base_url);
+        trace!("ListNodes call to {}", &url);
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#read-node
+    /// Method	Path	Produces
+    /// GET	/v1/node/:node_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	node:read
+    pub async fn read_node(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(Node, QueryMeta), NomadError> {
+        let url = format!("{}/v1/node/{}", &self.base_url, id);
+        trace!("ReadNode call to {}", &url);
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#list-node-allocations
+    /// Method	Path	Produces
+    /// GET	/v1/node/:node_id/allocations	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// YES	node:read,namespace:read-job
+    pub async fn list_node_allocations(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> Result<(Vec<Allocation>, QueryMeta), NomadError> {
+        let url = format!("{}/v1/node/{}/allocations", &self.base_url, id);
+        trace!("ListNodeAllocations call to {}", &url);
+        self.send_query(self.query(&url, q)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#drain-node
+    /// This endpoint toggles the drain mode of the node. When draining is enabled, no further
+    /// allocations will be assigned to this node, and existing allocations will be migrated to
+    /// new nodes.
+    /// Method	Path	Produces
+    /// POST	/v1/node/:node_id/drain	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    ///
+    /// Passing `None` as `drain_spec` disables an ongoing drain; `mark_eligible` then makes the
+    /// node schedulable again.
+    pub async fn update_node_drain(
+        &self,
+        id: &str,
+        drain_spec: Option<DrainSpec>,
+        mark_eligible: bool,
+    ) -> Result<NodeUpdateResponse, NomadError> {
+        let url = format!("{}/v1/node/{}/drain", &self.base_url, id);
+        trace!("UpdateNodeDrain call to {}", &url);
+        let request = NodeUpdateDrainRequest {
+            node_id: id.to_string(),
+            drain_spec,
+            mark_eligible,
+            meta: None,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#toggle-node-eligibility
+    /// This endpoint toggles the scheduling eligibility of the node.
+    /// Method	Path	Produces
+    /// POST	/v1/node/:node_id/eligibility	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    pub async fn update_node_eligibility(
+        &self,
+        id: &str,
+        eligible: bool,
+    ) -> Result<NodeUpdateResponse, NomadError> {
+        let url = format!("{}/v1/node/{}/eligibility", &self.base_url, id);
+        trace!("UpdateNodeEligibility call to {}", &url);
+        let request = NodeUpdateEligibilityRequest {
+            node_id: id.to_string(),
+            eligibility: if eligible { "eligible" } else { "ineligible" }.to_string(),
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#purge-node
+    /// This endpoint purges a node from the system. Nodes can still join the cluster if they
+    /// are alive.
+    /// Method	Path	Produces
+    /// POST	/v1/node/:node_id/purge	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    pub async fn purge_node(&self, id: &str) -> Result<NodeUpdateResponse, NomadError> {
+        let url = format!("{}/v1/node/{}/purge", &self.base_url, id);
+        trace!("PurgeNode call to {}", &url);
+        self.send(self.request(Method::POST, &url)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/nodes#create-node-evaluation
+    /// This endpoint creates a new evaluation for the given node. This can be used to force a
+    /// run of the scheduling logic.
+    /// Method	Path	Produces
+    /// POST	/v1/node/:node_id/evaluate	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    pub async fn evaluate_node(&self, id: &str) -> Result<NodeUpdateResponse, NomadError> {
+        let url = format!("{}/v1/node/{}/evaluate", &self.base_url, id);
+        trace!("EvaluateNode call to {}", &url);
+        self.send(self.request(Method::POST, &url)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#read-stats
+    /// This endpoint queries the actual resources consumed on a node.
+    /// Method	Path	Produces
+    /// GET	/v1/client/stats	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:read
+    ///
+    /// `node_id` selects the node when the client is connected to a server, leave it unset to
+    /// read the stats of the agent the client is connected to.
+    pub async fn read_client_stats(&self, node_id: Option<&str>) -> Result<HostStats, NomadError> {
+        let url = format!("{}/v1/client/stats", &self.base_url);
+        trace!("ReadClientStats call to {}", &url);
+        self.send(self.request(Method::GET, &url).query(&node_param(node_id)))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#read-allocation-statistics
+    /// This endpoint reports the actual resources consumed by an allocation and its tasks.
+    /// Method	Path	Produces
+    /// GET	/v1/client/allocation/:alloc_id/stats	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-job
+    pub async fn read_allocation_stats(&self, id: &str) -> Result<AllocResourceUsage, NomadError> {
+        let url = format!("{}/v1/client/allocation/{}/stats", &self.base_url, id);
+        trace!("ReadAllocationStats call to {}", &url);
+        self.send(self.request(Method::GET, &url)).await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#gc-allocation
+    /// This endpoint forces a garbage collection of a particular, stopped allocation on a node.
+    /// Method	Path	Produces
+    /// GET	/v1/client/allocation/:alloc_id/gc	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:submit-job
+    pub async fn gc_allocation(&self, id: &str) -> Result<(), NomadError> {
+        let url = format!("{}/v1/client/allocation/{}/gc", &self.base_url, id);
+        trace!("GcAllocation call to {}", &url);
+        self.execute(self.request(Method::GET, &url)).await?;
+        Ok(())
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#gc-all-allocation
+    /// This endpoint forces a garbage collection of all stopped allocations on a node.
+    /// Method	Path	Produces
+    /// GET	/v1/client/gc	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    pub async fn gc_client(&self, node_id: Option<&str>) -> Result<(), NomadError> {
+        let url = format!("{}/v1/client/gc", &self.base_url);
+        trace!("GcClient call to {}", &url);
+        self.execute(self.request(Method::GET, &url).query(&node_param(node_id)))
             .await?;
-        info!("Response: {:?}", &response);
-        println!("Response: {:?}", &response);
-        let response = response.json::<CreateJobResponse>().await?;
+        Ok(())
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#read-dynamic-node-metadata
+    /// Method	Path	Produces
+    /// GET	/v1/client/metadata	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:read
+    pub async fn read_client_metadata(
+        &self,
+        node_id: Option<&str>,
+    ) -> Result<NodeMetaResponse, NomadError> {
+        let url = format!("{}/v1/client/metadata", &self.base_url);
+        trace!("ReadClientMetadata call to {}", &url);
+        self.send(self.request(Method::GET, &url).query(&node_param(node_id)))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#update-dynamic-node-metadata
+    /// Method	Path	Produces
+    /// POST	/v1/client/metadata	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	node:write
+    ///
+    /// Keys mapped to `None` are removed from the node metadata. The updated metadata is
+    /// available to constraints once the node registers it with the servers.
+    pub async fn update_client_metadata(
+        &self,
+        node_id: Option<&str>,
+        meta: HashMap<String, Option<String>>,
+    ) -> Result<NodeMetaResponse, NomadError> {
+        let url = format!("{}/v1/client/metadata", &self.base_url);
+        trace!("UpdateClientMetadata call to {}", &url);
+        let request = NodeMetaApplyRequest {
+            node_id: node_id.map(str::to_string),
+            meta,
+        };
+        self.send(self.request(Method::POST, &url).json(&request))
+            .await
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// TLS settings of the client, `None` when the defaults are used.
+    pub(crate) fn tls_config(&self) -> Option<Arc<rustls::ClientConfig>> {
+        self.tls.clone()
+    }
+
+    /// Starts a request carrying the ACL token and the default region and namespace.
+    pub(crate) fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.request_in(method, url, None, None)
+    }
+
+    /// Starts a read carrying the ACL token and the parameters of `q`.
+    pub(crate) fn query(&self, url: &str, q: &QueryOptions) -> RequestBuilder {
+        self.request_in(Method::GET, url, q.region.as_ref(), q.namespace.as_ref())
+            .query(&q.to_params())
+    }
+
+    fn request_in(
+        &self,
+        method: Method,
+        url: &str,
+        region: Option<&String>,
+        namespace: Option<&String>,
+    ) -> RequestBuilder {
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .header("X-Nomad-Token", &self.authorization_token);
+        if let Some(region) = region.or(self.region.as_ref()) {
+            request = request.query(&[("region", region)]);
+        }
+        if let Some(namespace) = namespace.or(self.namespace.as_ref()) {
+            request = request.query(&[("namespace", namespace)]);
+        }
+        request
+    }
+
+    /// Sends `request` and decodes the JSON body into `T`.
+    pub(crate) async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, NomadError> {
+        let response = self.execute(request).await?;
+        let body = response.bytes().await?;
+        decode(&body)
+    }
 
+    /// Sends a read built by [`NomadClient::query`] and decodes the JSON body into `T`, along
+    /// with the [`QueryMeta`] taken from the response headers.
+    pub(crate) async fn send_query<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<(T, QueryMeta), NomadError> {
+        let response = self.execute(request).await?;
+        let meta = QueryMeta::from_headers(response.headers());
+        let body = response.bytes().await?;
+        Ok((decode(&body)?, meta))
+    }
+
+    /// Sends `request`, turning non-2xx answers into the matching [`NomadError`] variant
+    /// carrying the body Nomad sent.
+    pub(crate) async fn execute(&self, request: RequestBuilder) -> Result<Response, NomadError> {
+        let response = request.send().await?;
+        let status = response.status();
+        debug!("Response: {}", status);
+        if !status.is_success() {
+            let body = response.text().await?;
+            return Err(NomadError::from_status(status, body));
+        }
         Ok(response)
     }
 }
+
+/// `node_id` parameter of the client endpoints, used by servers to forward the request.
+fn node_param(node_id: Option<&str>) -> Vec<(&str, &str)> {
+    node_id.map(|id| ("node_id", id)).into_iter().collect()
+}
+
+/// Decodes a JSON body, recording the path of the field that failed to deserialize.
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, NomadError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| NomadError::Deserialization {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+        body: String::from_utf8_lossy(body).into_owned(),
+    })
+}
+
+/// Flattens dispatch meta into the string map Nomad expects.
+pub(crate) fn dispatch_meta<M: Serialize + ?Sized>(
+    meta: &M,
+) -> Result<HashMap<String, String>, NomadError> {
+    let value = serde_json::to_value(meta)
+        .map_err(|e| NomadError::InvalidRequest(format!("cannot serialize meta: {}", e)))?;
+    let map = match value {
+        serde_json::Value::Object(map) => map,
+        serde_json::Value::Null => return Ok(HashMap::new()),
+        other => {
+            return Err(NomadError::InvalidRequest(format!(
+                "meta must serialize to a map, got {}",
+                other
+            )))
+        }
+    };
+    Ok(map
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some((key, s)),
+            other => Some((key, other.to_string())),
+        })
+        .collect())
+}
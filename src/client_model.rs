@@ -1 +1,276 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// https://www.nomadproject.io/api-docs/client#read-stats
+/// Method	Path	Produces
+/// GET	/v1/client/stats	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostStats {
+    #[serde(rename = "Memory")]
+    #[serde(default)]
+    pub memory: Option<HostMemoryStats>,
+    #[serde(rename = "CPU")]
+    #[serde(default)]
+    pub cpu: Option<Vec<HostCpuStats>>,
+    #[serde(rename = "DiskStats")]
+    #[serde(default)]
+    pub disk_stats: Option<Vec<HostDiskStats>>,
+    #[serde(rename = "AllocDirStats")]
+    #[serde(default)]
+    pub alloc_dir_stats: Option<HostDiskStats>,
+    #[serde(rename = "DeviceStats")]
+    #[serde(default)]
+    pub device_stats: Option<Vec<DeviceGroupStats>>,
+    /// Seconds since the host booted.
+    #[serde(rename = "Uptime")]
+    #[serde(default)]
+    pub uptime: u64,
+    /// Unix time in nanoseconds.
+    #[serde(rename = "Timestamp")]
+    #[serde(default)]
+    pub timestamp: i64,
+    #[serde(rename = "CPUTicksConsumed")]
+    #[serde(default)]
+    pub cpu_ticks_consumed: f64,
+}
+
+/// Memory of the host, in bytes.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostMemoryStats {
+    #[serde(rename = "Total")]
+    pub total: u64,
+    #[serde(rename = "Available")]
+    pub available: u64,
+    #[serde(rename = "Used")]
+    pub used: u64,
+    #[serde(rename = "Free")]
+    pub free: u64,
+}
+
+/// Usage of a single core, in percent.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostCpuStats {
+    #[serde(rename = "CPU")]
+    pub cpu: String,
+    #[serde(rename = "User")]
+    pub user: f64,
+    #[serde(rename = "System")]
+    pub system: f64,
+    #[serde(rename = "Idle")]
+    pub idle: f64,
+    #[serde(rename = "Total")]
+    pub total: f64,
+}
+
+/// Usage of a mounted file system, sizes in bytes.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostDiskStats {
+    #[serde(rename = "Device")]
+    #[serde(default)]
+    pub device: String,
+    #[serde(rename = "Mountpoint")]
+    #[serde(default)]
+    pub mountpoint: String,
+    #[serde(rename = "Size")]
+    pub size: u64,
+    #[serde(rename = "Used")]
+    pub used: u64,
+    #[serde(rename = "Available")]
+    pub available: u64,
+    #[serde(rename = "UsedPercent")]
+    pub used_percent: f64,
+    #[serde(rename = "InodesUsedPercent")]
+    pub inodes_used_percent: f64,
+}
+
+/// Statistics reported by a device plugin for one group of devices, e.g. the GPUs of a vendor.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceGroupStats {
+    #[serde(rename = "Vendor")]
+    pub vendor: String,
+    #[serde(rename = "Type")]
+    pub type_field: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// Keyed by device instance ID.
+    #[serde(rename = "InstanceStats")]
+    #[serde(default)]
+    pub instance_stats: Option<HashMap<String, DeviceStats>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceStats {
+    #[serde(rename = "Summary")]
+    #[serde(default)]
+    pub summary: Option<StatValue>,
+    /// Nested statistics specific to the device plugin.
+    #[serde(rename = "Stats")]
+    #[serde(default)]
+    pub stats: Option<serde_json::Value>,
+    #[serde(rename = "Timestamp")]
+    #[serde(default)]
+    pub timestamp: String,
+}
+
+/// A single statistic, e.g. GPU memory `{"IntNumeratorVal": 512, "IntDenominatorVal": 15109,
+/// "Unit": "MiB"}`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatValue {
+    #[serde(rename = "FloatNumeratorVal")]
+    pub float_numerator_val: Option<f64>,
+    #[serde(rename = "FloatDenominatorVal")]
+    pub float_denominator_val: Option<f64>,
+    #[serde(rename = "IntNumeratorVal")]
+    pub int_numerator_val: Option<i64>,
+    #[serde(rename = "IntDenominatorVal")]
+    pub int_denominator_val: Option<i64>,
+    #[serde(rename = "StringVal")]
+    pub string_val: Option<String>,
+    #[serde(rename = "BoolVal")]
+    pub bool_val: Option<bool>,
+    #[serde(rename = "Unit")]
+    #[serde(default)]
+    pub unit: String,
+    #[serde(rename = "Desc")]
+    #[serde(default)]
+    pub desc: String,
+}
+
+/// https://www.nomadproject.io/api-docs/client#read-allocation-statistics
+/// Method	Path	Produces
+/// GET	/v1/client/allocation/:alloc_id/stats	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocResourceUsage {
+    /// Sum of the usage of every task.
+    #[serde(rename = "ResourceUsage")]
+    #[serde(default)]
+    pub resource_usage: Option<ResourceUsage>,
+    /// Keyed by task name.
+    #[serde(rename = "Tasks")]
+    #[serde(default)]
+    pub tasks: HashMap<String, TaskResourceUsage>,
+    /// Unix time in nanoseconds.
+    #[serde(rename = "Timestamp")]
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskResourceUsage {
+    #[serde(rename = "ResourceUsage")]
+    #[serde(default)]
+    pub resource_usage: Option<ResourceUsage>,
+    /// Unix time in nanoseconds.
+    #[serde(rename = "Timestamp")]
+    #[serde(default)]
+    pub timestamp: i64,
+    /// Usage per process, keyed by PID. Only reported by some drivers.
+    #[serde(rename = "Pids")]
+    #[serde(default)]
+    pub pids: Option<HashMap<String, ResourceUsage>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    #[serde(rename = "MemoryStats")]
+    #[serde(default)]
+    pub memory_stats: Option<MemoryStats>,
+    #[serde(rename = "CpuStats")]
+    #[serde(default)]
+    pub cpu_stats: Option<CpuStats>,
+    #[serde(rename = "DeviceStats")]
+    #[serde(default)]
+    pub device_stats: Option<Vec<DeviceGroupStats>>,
+}
+
+/// Memory used by a task, in bytes. `measured` lists the fields the driver actually reports,
+/// the others are left at 0.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryStats {
+    #[serde(rename = "RSS")]
+    #[serde(default)]
+    pub rss: u64,
+    #[serde(rename = "Cache")]
+    #[serde(default)]
+    pub cache: u64,
+    #[serde(rename = "Swap")]
+    #[serde(default)]
+    pub swap: u64,
+    #[serde(rename = "MappedFile")]
+    #[serde(default)]
+    pub mapped_file: u64,
+    #[serde(rename = "Usage")]
+    #[serde(default)]
+    pub usage: u64,
+    #[serde(rename = "MaxUsage")]
+    #[serde(default)]
+    pub max_usage: u64,
+    #[serde(rename = "KernelUsage")]
+    #[serde(default)]
+    pub kernel_usage: u64,
+    #[serde(rename = "KernelMaxUsage")]
+    #[serde(default)]
+    pub kernel_max_usage: u64,
+    #[serde(rename = "Measured")]
+    #[serde(default)]
+    pub measured: Option<Vec<String>>,
+}
+
+/// CPU used by a task. `percent` is relative to a single core, `total_ticks` is in MHz like the
+/// `cpu` resource of the task.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuStats {
+    #[serde(rename = "SystemMode")]
+    #[serde(default)]
+    pub system_mode: f64,
+    #[serde(rename = "UserMode")]
+    #[serde(default)]
+    pub user_mode: f64,
+    #[serde(rename = "TotalTicks")]
+    #[serde(default)]
+    pub total_ticks: f64,
+    #[serde(rename = "ThrottledPeriods")]
+    #[serde(default)]
+    pub throttled_periods: u64,
+    #[serde(rename = "ThrottledTime")]
+    #[serde(default)]
+    pub throttled_time: u64,
+    #[serde(rename = "Percent")]
+    #[serde(default)]
+    pub percent: f64,
+    #[serde(rename = "Measured")]
+    #[serde(default)]
+    pub measured: Option<Vec<String>>,
+}
+
+/// https://www.nomadproject.io/api-docs/client#read-dynamic-node-metadata
+/// Method	Path	Produces
+/// GET	/v1/client/metadata	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetaResponse {
+    /// Effective metadata, static values overridden by dynamic ones.
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: HashMap<String, String>,
+    /// Metadata set through [`crate::client::NomadClient::update_client_metadata`]. A `None`
+    /// value removes a key from the static metadata.
+    #[serde(rename = "Dynamic")]
+    #[serde(default)]
+    pub dynamic: HashMap<String, Option<String>>,
+    /// Metadata from the agent configuration.
+    #[serde(rename = "Static")]
+    #[serde(default)]
+    pub static_meta: HashMap<String, String>,
+}
+
+/// https://www.nomadproject.io/api-docs/client#update-dynamic-node-metadata
+/// Method	Path	Produces
+/// POST	/v1/client/metadata	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetaApplyRequest {
+    #[serde(rename = "NodeID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(rename = "Meta")]
+    pub meta: HashMap<String, Option<String>>,
+}
@@ -0,0 +1,353 @@
+//! Placement rules of jobs, task groups, tasks and devices.
+//!
+//! https://www.nomadproject.io/docs/job-specification/constraint
+//!
+//! Constraints and affinities compare an attribute of the node, the left target, with a value,
+//! the right target:
+//!
+//! ```
+//! use nomad_api::constraints::{Affinity, Constraint, Spread};
+//!
+//! let constraints = vec![
+//!     Constraint::attr("${attr.kernel.name}").eq("linux"),
+//!     Constraint::attr("${device.vendor}").eq("nvidia"),
+//!     Constraint::attr("${meta.cuda}").semver(">= 12.0"),
+//!     Constraint::distinct_hosts(),
+//! ];
+//! let affinity = Affinity::attr("${node.class}").eq("a100").weight(80);
+//! let spread = Spread::new("${node.datacenter}")
+//!     .target("eu-west-1", 70)
+//!     .target("eu-central-1", 30);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Comparison of a constraint or affinity.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Operand {
+    /// Also read from `==` and `is`.
+    Equal,
+    /// Also read from `not`.
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Regexp,
+    /// Version comparison in Nomad's version syntax, e.g. `>= 0.1.0, < 0.2`.
+    Version,
+    /// Version comparison following Semantic Versioning 2.0, prereleases included.
+    Semver,
+    /// The comma separated attribute contains every value of the comma separated right target.
+    SetContains,
+    /// Same as [`Operand::SetContains`].
+    SetContainsAll,
+    /// The comma separated attribute contains any value of the comma separated right target.
+    SetContainsAny,
+    /// No two allocations of the job or group on the same node.
+    DistinctHosts,
+    /// At most the right target, 1 if empty, allocations per value of the attribute.
+    DistinctProperty,
+    IsSet,
+    IsNotSet,
+    /// An operand this crate does not know about.
+    Other(String),
+}
+
+impl Operand {
+    /// The operand as Nomad spells it.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Operand::Equal => "=",
+            Operand::NotEqual => "!=",
+            Operand::Greater => ">",
+            Operand::GreaterOrEqual => ">=",
+            Operand::Less => "<",
+            Operand::LessOrEqual => "<=",
+            Operand::Regexp => "regexp",
+            Operand::Version => "version",
+            Operand::Semver => "semver",
+            Operand::SetContains => "set_contains",
+            Operand::SetContainsAll => "set_contains_all",
+            Operand::SetContainsAny => "set_contains_any",
+            Operand::DistinctHosts => "distinct_hosts",
+            Operand::DistinctProperty => "distinct_property",
+            Operand::IsSet => "is_set",
+            Operand::IsNotSet => "is_not_set",
+            Operand::Other(operand) => operand,
+        }
+    }
+}
+
+impl From<&str> for Operand {
+    fn from(operand: &str) -> Self {
+        match operand {
+            "=" | "==" | "is" => Operand::Equal,
+            "!=" | "not" => Operand::NotEqual,
+            ">" => Operand::Greater,
+            ">=" => Operand::GreaterOrEqual,
+            "<" => Operand::Less,
+            "<=" => Operand::LessOrEqual,
+            "regexp" => Operand::Regexp,
+            "version" => Operand::Version,
+            "semver" => Operand::Semver,
+            "set_contains" => Operand::SetContains,
+            "set_contains_all" => Operand::SetContainsAll,
+            "set_contains_any" => Operand::SetContainsAny,
+            "distinct_hosts" => Operand::DistinctHosts,
+            "distinct_property" => Operand::DistinctProperty,
+            "is_set" => Operand::IsSet,
+            "is_not_set" => Operand::IsNotSet,
+            operand => Operand::Other(operand.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Operand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Operand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let operand = String::deserialize(deserializer)?;
+        Ok(Operand::from(operand.as_str()))
+    }
+}
+
+/// https://www.nomadproject.io/docs/job-specification/constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Constraint {
+    #[serde(rename = "LTarget")]
+    #[serde(default)]
+    pub l_target: String,
+    #[serde(rename = "RTarget")]
+    #[serde(default)]
+    pub r_target: String,
+    #[serde(rename = "Operand")]
+    pub operand: Operand,
+}
+
+impl Constraint {
+    pub fn new(l_target: impl Into<String>, operand: Operand, r_target: impl Into<String>) -> Self {
+        Constraint {
+            l_target: l_target.into(),
+            r_target: r_target.into(),
+            operand,
+        }
+    }
+
+    /// Starts a constraint on `attribute`, an interpolated node attribute like
+    /// `${attr.kernel.name}` or `${meta.rack}`.
+    pub fn attr(attribute: impl Into<String>) -> Attribute<Constraint> {
+        Attribute {
+            l_target: attribute.into(),
+            build: |l_target, operand, r_target| Constraint {
+                l_target,
+                r_target,
+                operand,
+            },
+        }
+    }
+
+    /// Places every allocation on a different node.
+    pub fn distinct_hosts() -> Self {
+        Constraint::new("", Operand::DistinctHosts, "true")
+    }
+
+    /// Places at most `limit`, 1 if `None`, allocations per value of `attribute`.
+    pub fn distinct_property(attribute: impl Into<String>, limit: Option<u32>) -> Self {
+        Constraint::new(
+            attribute,
+            Operand::DistinctProperty,
+            limit.map(|limit| limit.to_string()).unwrap_or_default(),
+        )
+    }
+}
+
+/// https://www.nomadproject.io/docs/job-specification/affinity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Affinity {
+    #[serde(rename = "LTarget")]
+    #[serde(default)]
+    pub l_target: String,
+    #[serde(rename = "RTarget")]
+    #[serde(default)]
+    pub r_target: String,
+    #[serde(rename = "Operand")]
+    pub operand: Operand,
+    /// From -100 to 100, negative weights avoid matching nodes.
+    #[serde(rename = "Weight")]
+    pub weight: i8,
+}
+
+impl Affinity {
+    /// Weight Nomad gives affinities that do not set one.
+    pub const DEFAULT_WEIGHT: i8 = 50;
+
+    /// Starts an affinity on `attribute` with [`Affinity::DEFAULT_WEIGHT`], see
+    /// [`Constraint::attr`].
+    pub fn attr(attribute: impl Into<String>) -> Attribute<Affinity> {
+        Attribute {
+            l_target: attribute.into(),
+            build: |l_target, operand, r_target| Affinity {
+                l_target,
+                r_target,
+                operand,
+                weight: Affinity::DEFAULT_WEIGHT,
+            },
+        }
+    }
+
+    pub fn weight(mut self, weight: i8) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Left target of a constraint or an affinity, finished by one of the comparisons.
+#[derive(Debug, Clone)]
+pub struct Attribute<T> {
+    l_target: String,
+    build: fn(String, Operand, String) -> T,
+}
+
+impl<T> Attribute<T> {
+    pub fn compare(self, operand: Operand, value: impl Into<String>) -> T {
+        (self.build)(self.l_target, operand, value.into())
+    }
+
+    pub fn eq(self, value: impl Into<String>) -> T {
+        self.compare(Operand::Equal, value)
+    }
+
+    pub fn ne(self, value: impl Into<String>) -> T {
+        self.compare(Operand::NotEqual, value)
+    }
+
+    pub fn gt(self, value: impl Into<String>) -> T {
+        self.compare(Operand::Greater, value)
+    }
+
+    pub fn ge(self, value: impl Into<String>) -> T {
+        self.compare(Operand::GreaterOrEqual, value)
+    }
+
+    pub fn lt(self, value: impl Into<String>) -> T {
+        self.compare(Operand::Less, value)
+    }
+
+    pub fn le(self, value: impl Into<String>) -> T {
+        self.compare(Operand::LessOrEqual, value)
+    }
+
+    pub fn regexp(self, pattern: impl Into<String>) -> T {
+        self.compare(Operand::Regexp, pattern)
+    }
+
+    pub fn version(self, requirement: impl Into<String>) -> T {
+        self.compare(Operand::Version, requirement)
+    }
+
+    pub fn semver(self, requirement: impl Into<String>) -> T {
+        self.compare(Operand::Semver, requirement)
+    }
+
+    /// The attribute contains every value of `values`.
+    pub fn set_contains<I, S>(self, values: I) -> T
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.compare(Operand::SetContains, join(values))
+    }
+
+    /// The attribute contains any value of `values`.
+    pub fn set_contains_any<I, S>(self, values: I) -> T
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.compare(Operand::SetContainsAny, join(values))
+    }
+
+    pub fn is_set(self) -> T {
+        self.compare(Operand::IsSet, "")
+    }
+
+    pub fn is_not_set(self) -> T {
+        self.compare(Operand::IsNotSet, "")
+    }
+}
+
+fn join<I, S>(values: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    values
+        .into_iter()
+        .map(|value| value.as_ref().to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// https://www.nomadproject.io/docs/job-specification/spread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spread {
+    #[serde(rename = "Attribute")]
+    pub attribute: String,
+    /// From 0 to 100, relative to the other spreads.
+    #[serde(rename = "Weight")]
+    pub weight: i8,
+    /// Share of the allocations per value, spread evenly over every value if empty.
+    #[serde(rename = "SpreadTarget")]
+    #[serde(default)]
+    pub spread_target: Option<Vec<SpreadTarget>>,
+}
+
+impl Spread {
+    /// Weight Nomad gives spreads that do not set one.
+    pub const DEFAULT_WEIGHT: i8 = 50;
+
+    /// Spreads allocations evenly over the values of `attribute`.
+    pub fn new(attribute: impl Into<String>) -> Self {
+        Spread {
+            attribute: attribute.into(),
+            weight: Spread::DEFAULT_WEIGHT,
+            spread_target: None,
+        }
+    }
+
+    pub fn weight(mut self, weight: i8) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Places `percent` of the allocations on nodes where the attribute is `value`.
+    pub fn target(mut self, value: impl Into<String>, percent: u8) -> Self {
+        self.spread_target
+            .get_or_insert_with(Vec::new)
+            .push(SpreadTarget {
+                value: value.into(),
+                percent,
+            });
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpreadTarget {
+    #[serde(rename = "Value")]
+    pub value: String,
+    #[serde(rename = "Percent")]
+    pub percent: u8,
+}
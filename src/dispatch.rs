@@ -0,0 +1,201 @@
+//! Dispatching a parameterized job and waiting for it to finish.
+//!
+//! [`NomadClient::run_dispatched`] follows the allocations of the dispatched job with the
+//! watchers of [`crate::watch`] and reports how each task ended.
+//!
+//! ```no_run
+//! # async fn run(client: nomad_api::client::NomadClient) -> Result<(), nomad_api::error::NomadError> {
+//! use std::collections::HashMap;
+//! use std::time::Duration;
+//!
+//! let mut meta = HashMap::new();
+//! meta.insert("numpire_image", "localhost:5000/agridos-worker");
+//! let outcome = client
+//!     .run_dispatched("numpire-worker-gpu", None, &meta, Duration::from_secs(3600))
+//!     .await?;
+//! for (task, result) in &outcome.tasks {
+//!     println!("{} exited with {:?}", task, result.exit_code);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::NomadClient;
+use crate::error::NomadError;
+use crate::jobs::{AllocationListStub, TaskState};
+use crate::query::QueryOptions;
+use crate::status::{AllocClientStatus, TaskStateKind};
+use futures::stream::StreamExt;
+use log::{debug, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Zero time Nomad reports for tasks that did not start or finish.
+const ZERO_TIME: &str = "0001-01-01T00:00:00Z";
+
+/// How the allocation of a dispatched job ended, see [`NomadClient::run_dispatched`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchOutcome {
+    pub dispatched_job_id: String,
+    pub eval_id: Option<String>,
+    pub alloc_id: String,
+    /// `Complete`, `Failed` or `Lost`.
+    pub client_status: AllocClientStatus,
+    /// Keyed by task name.
+    pub tasks: HashMap<String, TaskOutcome>,
+}
+
+impl DispatchOutcome {
+    /// Whether the allocation completed and every task exited with 0.
+    pub fn succeeded(&self) -> bool {
+        self.client_status == AllocClientStatus::Complete
+            && self
+                .tasks
+                .values()
+                .all(|task| !task.failed && task.exit_code.unwrap_or(0) == 0)
+    }
+}
+
+/// Final state of a task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskOutcome {
+    pub state: TaskStateKind,
+    pub failed: bool,
+    /// Exit code of the last run, `None` if the task never exited by itself, e.g. because its
+    /// image could not be pulled.
+    pub exit_code: Option<i64>,
+    /// Whether the last run was killed for exceeding its memory limit.
+    pub oom_killed: bool,
+    /// Message of the event that failed the task.
+    pub failure_reason: Option<String>,
+    pub restarts: i64,
+    /// RFC 3339 timestamps.
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl From<&TaskState> for TaskOutcome {
+    fn from(state: &TaskState) -> Self {
+        let events = state.events.as_deref().unwrap_or_default();
+        let terminated = events
+            .iter()
+            .rev()
+            .find(|event| event.type_field == "Terminated");
+        let failed = state.failed.unwrap_or(false);
+        let failure_reason = if failed {
+            events
+                .iter()
+                .rev()
+                .find(|event| event.fails_task)
+                .or_else(|| events.last())
+                .map(|event| {
+                    if event.display_message.is_empty() {
+                        event.message.clone()
+                    } else {
+                        event.display_message.clone()
+                    }
+                })
+        } else {
+            None
+        };
+        TaskOutcome {
+            state: state.state.clone(),
+            failed,
+            exit_code: terminated.map(|event| {
+                event
+                    .details
+                    .exit_code
+                    .as_deref()
+                    .and_then(|code| code.parse().ok())
+                    .unwrap_or(event.exit_code)
+            }),
+            oom_killed: terminated.and_then(|event| event.details.oom_killed.as_deref())
+                == Some("true"),
+            failure_reason,
+            restarts: state.restarts,
+            started_at: time(state.started_at.as_deref()),
+            finished_at: time(state.finished_at.as_ref().and_then(|time| time.as_str())),
+        }
+    }
+}
+
+fn time(time: Option<&str>) -> Option<String> {
+    time.filter(|time| !time.is_empty() && *time != ZERO_TIME)
+        .map(str::to_string)
+}
+
+impl NomadClient {
+    /// Dispatches `job` like [`NomadClient::dispatch_job_raw`] and waits until the allocation of
+    /// the dispatch stopped running.
+    ///
+    /// Only the first allocation is reported: a failed allocation is returned as is, even if
+    /// Nomad reschedules it. Waiting longer than `timeout` fails with [`NomadError::Timeout`],
+    /// the dispatched job keeps running in that case.
+    pub async fn run_dispatched<M: Serialize + ?Sized>(
+        &self,
+        job: &str,
+        payload: Option<&[u8]>,
+        meta: &M,
+        timeout: Duration,
+    ) -> Result<DispatchOutcome, NomadError> {
+        let dispatched = self.dispatch_job_raw(job, payload, meta, None).await?;
+        debug!("Dispatched {}", &dispatched.dispatched_job_id);
+        let wait = self.wait_allocation(&dispatched.dispatched_job_id);
+        let allocation = match tokio::time::timeout(timeout, wait).await {
+            Ok(allocation) => allocation?,
+            Err(_) => {
+                warn!(
+                    "{} did not finish within {:?}",
+                    &dispatched.dispatched_job_id, timeout
+                );
+                return Err(NomadError::Timeout(timeout));
+            }
+        };
+        let tasks = allocation
+            .task_states
+            .iter()
+            .flatten()
+            .map(|(name, state)| (name.clone(), TaskOutcome::from(state)))
+            .collect();
+        Ok(DispatchOutcome {
+            dispatched_job_id: dispatched.dispatched_job_id,
+            eval_id: dispatched.eval_id,
+            alloc_id: allocation.id,
+            client_status: allocation.client_status,
+            tasks,
+        })
+    }
+
+    /// Waits for the first allocation of the job to stop running.
+    ///
+    /// The job is watched rather than the evaluation of the dispatch: when the cluster lacks
+    /// capacity that evaluation is blocked and the allocation is placed by a later one.
+    async fn wait_allocation(&self, job_id: &str) -> Result<AllocationListStub, NomadError> {
+        let mut allocations = self.watch_job_allocations(job_id, &QueryOptions::default());
+        while let Some(allocations) = allocations.next().await {
+            let allocations = match allocations {
+                Ok(allocations) => allocations,
+                // The watcher retries these itself.
+                Err(e) if e.is_retryable() => continue,
+                Err(e) => return Err(e),
+            };
+            let first = allocations
+                .into_iter()
+                .min_by_key(|allocation| allocation.create_index);
+            if let Some(allocation) = first {
+                if allocation.client_status.is_terminal() {
+                    return Ok(allocation);
+                }
+                debug!(
+                    "Allocation {} is {}",
+                    &allocation.id, &allocation.client_status
+                );
+            }
+        }
+        Err(NomadError::InvalidRequest(format!(
+            "watch of the allocations of {} ended",
+            job_id
+        )))
+    }
+}
@@ -0,0 +1,146 @@
+use crate::jobs::DispatchViolation;
+use reqwest::StatusCode;
+use std::fmt;
+
+/// Error returned by every call on [`crate::client::NomadClient`].
+///
+/// Nomad answers ACL denials with `403` and unknown objects with `404`, both of which get their
+/// own variant so callers can match on them instead of inspecting the status code. Every other
+/// non-2xx response ends up in [`NomadError::Http`] together with the body Nomad sent.
+#[derive(Debug)]
+pub enum NomadError {
+    /// The client could not be configured, e.g. an unreadable CA certificate.
+    Config(String),
+    /// The request cannot be completed as asked, e.g. a dispatch payload above Nomad's limit,
+    /// rejected before being sent, or a watch on an answer without an `X-Nomad-Index`.
+    InvalidRequest(String),
+    /// A dispatch was rejected before being sent because it does not match the `parameterized`
+    /// block of the job, see [`crate::client::NomadClient::dispatch_job_checked`].
+    InvalidDispatch {
+        job: String,
+        violations: Vec<DispatchViolation>,
+    },
+    /// The request never produced a response: connection refused, timeout, TLS failure, ...
+    Transport(reqwest::Error),
+    /// A WebSocket session, e.g. [`crate::client::NomadClient::exec`], failed or was closed
+    /// before it completed.
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    /// Nomad answered with a status code that has no dedicated variant.
+    Http { status: StatusCode, body: String },
+    /// `403 Forbidden`, usually "Permission denied" because the ACL token lacks a capability.
+    PermissionDenied { body: String },
+    /// `404 Not Found`, e.g. "job not found" or "alloc not found".
+    NotFound { body: String },
+    /// A wait such as [`crate::client::NomadClient::run_dispatched`] did not complete in time.
+    Timeout(std::time::Duration),
+    /// The response was 2xx but its body did not match the expected model.
+    ///
+    /// `path` is the location of the offending field using Nomad's field names, for example
+    /// `Job.TaskGroups[0].Count`.
+    Deserialization {
+        path: String,
+        source: serde_json::Error,
+        body: String,
+    },
+}
+
+impl NomadError {
+    /// Builds the error for a non-2xx response.
+    pub(crate) fn from_status(status: StatusCode, body: String) -> Self {
+        match status {
+            StatusCode::FORBIDDEN => NomadError::PermissionDenied { body },
+            StatusCode::NOT_FOUND => NomadError::NotFound { body },
+            _ => NomadError::Http { status, body },
+        }
+    }
+
+    /// HTTP status of the response, if Nomad answered at all.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            NomadError::Config(_) => None,
+            NomadError::InvalidRequest(_) => None,
+            NomadError::InvalidDispatch { .. } => None,
+            NomadError::Transport(e) => e.status(),
+            NomadError::WebSocket(_) => None,
+            NomadError::Http { status, .. } => Some(*status),
+            NomadError::PermissionDenied { .. } => Some(StatusCode::FORBIDDEN),
+            NomadError::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            NomadError::Timeout(_) => None,
+            NomadError::Deserialization { .. } => None,
+        }
+    }
+
+    /// Whether repeating the same request may succeed.
+    ///
+    /// This is true for transport and WebSocket failures, `429 Too Many Requests` and `5xx`
+    /// answers (for instance while the cluster has no leader). ACL denials, unknown objects,
+    /// other `4xx` answers and malformed bodies will fail the same way again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            NomadError::Transport(_) | NomadError::WebSocket(_) => true,
+            NomadError::Http { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for NomadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NomadError::Config(message) => write!(f, "invalid configuration: {}", message),
+            NomadError::InvalidRequest(message) => write!(f, "invalid request: {}", message),
+            NomadError::InvalidDispatch { job, violations } => {
+                write!(f, "invalid dispatch of {}: ", job)?;
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", violation)?;
+                }
+                Ok(())
+            }
+            NomadError::Transport(e) => write!(f, "request to nomad failed: {}", e),
+            NomadError::WebSocket(e) => write!(f, "websocket to nomad failed: {}", e),
+            NomadError::Http { status, body } => {
+                write!(f, "nomad responded with {}: {}", status, body.trim())
+            }
+            NomadError::PermissionDenied { body } => {
+                write!(f, "permission denied: {}", body.trim())
+            }
+            NomadError::NotFound { body } => write!(f, "not found: {}", body.trim()),
+            NomadError::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+            NomadError::Deserialization { path, source, .. } => {
+                write!(
+                    f,
+                    "failed to decode nomad response at `{}`: {}",
+                    path, source
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NomadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NomadError::Transport(e) => Some(e),
+            NomadError::WebSocket(e) => Some(e.as_ref()),
+            NomadError::Deserialization { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for NomadError {
+    fn from(e: reqwest::Error) -> Self {
+        NomadError::Transport(e)
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for NomadError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        NomadError::WebSocket(Box::new(e))
+    }
+}
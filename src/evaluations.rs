@@ -0,0 +1,78 @@
+use crate::jobs::Metrics;
+use crate::status::EvalStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// https://www.nomadproject.io/api-docs/evaluations#read-evaluation
+/// Method	Path                        Produces
+/// GET    /v1/evaluation/:eval_id    application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Priority")]
+    pub priority: i64,
+    #[serde(rename = "Type")]
+    pub type_field: String,
+    #[serde(rename = "TriggeredBy")]
+    pub triggered_by: String,
+    #[serde(rename = "Namespace")]
+    pub namespace: String,
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "JobModifyIndex")]
+    #[serde(default)]
+    pub job_modify_index: i64,
+    #[serde(rename = "NodeID")]
+    pub node_id: Option<String>,
+    #[serde(rename = "NodeModifyIndex")]
+    #[serde(default)]
+    pub node_modify_index: i64,
+    #[serde(rename = "DeploymentID")]
+    pub deployment_id: Option<String>,
+    #[serde(rename = "Status")]
+    pub status: EvalStatus,
+    #[serde(rename = "StatusDescription")]
+    pub status_description: Option<String>,
+    #[serde(rename = "Wait")]
+    #[serde(default)]
+    pub wait: i64,
+    #[serde(rename = "WaitUntil")]
+    pub wait_until: Option<String>,
+    #[serde(rename = "NextEval")]
+    pub next_eval: Option<String>,
+    #[serde(rename = "PreviousEval")]
+    pub previous_eval: Option<String>,
+    #[serde(rename = "BlockedEval")]
+    pub blocked_eval: Option<String>,
+    #[serde(rename = "FailedTGAllocs")]
+    #[serde(default)]
+    pub failed_tg_allocs: Option<HashMap<String, Metrics>>,
+    #[serde(rename = "ClassEligibility")]
+    #[serde(default)]
+    pub class_eligibility: Option<HashMap<String, bool>>,
+    #[serde(rename = "EscapedComputedClass")]
+    #[serde(default)]
+    pub escaped_computed_class: bool,
+    #[serde(rename = "QuotaLimitReached")]
+    pub quota_limit_reached: Option<String>,
+    #[serde(rename = "AnnotatePlan")]
+    #[serde(default)]
+    pub annotate_plan: bool,
+    #[serde(rename = "QueuedAllocations")]
+    #[serde(default)]
+    pub queued_allocations: Option<HashMap<String, i64>>,
+    #[serde(rename = "SnapshotIndex")]
+    #[serde(default)]
+    pub snapshot_index: i64,
+    #[serde(rename = "CreateIndex")]
+    pub create_index: i64,
+    #[serde(rename = "ModifyIndex")]
+    pub modify_index: i64,
+    #[serde(rename = "CreateTime")]
+    #[serde(default)]
+    pub create_time: i64,
+    #[serde(rename = "ModifyTime")]
+    #[serde(default)]
+    pub modify_time: i64,
+}
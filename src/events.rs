@@ -0,0 +1,437 @@
+//! Client for the event stream.
+//!
+//! https://www.nomadproject.io/api-docs/events#event-stream
+//!
+//! The stream reconnects on its own when the connection drops or no heartbeat arrives for
+//! [`HEARTBEAT_TIMEOUT`], resuming after the last index it delivered. Nomad only keeps a
+//! bounded buffer of events and does not say when the requested index fell out of it, so when
+//! the stream resumes at a later index than requested an [`EventStreamItem::Gap`] is yielded
+//! before the next events: events may have been missed, but most often none were.
+
+use crate::client::NomadClient;
+use crate::error::NomadError;
+use crate::evaluations::Evaluation;
+use crate::jobs::{Allocation, Job};
+use crate::nodes::Node;
+use crate::query::QueryOptions;
+use crate::watch::backoff;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt};
+use log::{debug, warn};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// Nomad sends a heartbeat every 10 seconds, a connection that stays silent for longer than
+/// this is considered dead.
+pub const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(35);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Job,
+    Allocation,
+    Evaluation,
+    Deployment,
+    Node,
+    NodePool,
+    Service,
+    AclToken,
+    AclPolicy,
+    AclRole,
+    /// `*`, every topic.
+    All,
+    /// A topic this crate does not know about, kept as Nomad spells it.
+    Other(String),
+}
+
+impl Topic {
+    /// The topic as Nomad spells it.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Topic::Job => "Job",
+            Topic::Allocation => "Allocation",
+            Topic::Evaluation => "Evaluation",
+            Topic::Deployment => "Deployment",
+            Topic::Node => "Node",
+            Topic::NodePool => "NodePool",
+            Topic::Service => "Service",
+            Topic::AclToken => "ACLToken",
+            Topic::AclPolicy => "ACLPolicy",
+            Topic::AclRole => "ACLRole",
+            Topic::All => "*",
+            Topic::Other(topic) => topic,
+        }
+    }
+}
+
+impl From<&str> for Topic {
+    fn from(topic: &str) -> Self {
+        match topic {
+            "Job" => Topic::Job,
+            "Allocation" => Topic::Allocation,
+            "Evaluation" => Topic::Evaluation,
+            "Deployment" => Topic::Deployment,
+            "Node" => Topic::Node,
+            "NodePool" => Topic::NodePool,
+            "Service" => Topic::Service,
+            "ACLToken" => Topic::AclToken,
+            "ACLPolicy" => Topic::AclPolicy,
+            "ACLRole" => Topic::AclRole,
+            "*" => Topic::All,
+            topic => Topic::Other(topic.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Topic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Topic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let topic = String::deserialize(deserializer)?;
+        Ok(Topic::from(topic.as_str()))
+    }
+}
+
+/// Selects the events to stream.
+///
+/// ```
+/// use nomad_api::events::{EventStreamOptions, Topic};
+///
+/// let options = EventStreamOptions::default()
+///     .topic(Topic::Allocation, "*")
+///     .topic(Topic::Job, "numpire-worker-gpu")
+///     .namespace("*");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventStreamOptions {
+    /// Topics with their filter key, `*` for every key. No topic streams everything.
+    pub topics: Vec<(Topic, String)>,
+    /// Namespace to stream, `*` for all namespaces.
+    pub namespace: Option<String>,
+    /// Index to start streaming from.
+    pub index: Option<u64>,
+}
+
+impl EventStreamOptions {
+    pub fn topic(mut self, topic: Topic, key: impl Into<String>) -> Self {
+        self.topics.push((topic, key.into()));
+        self
+    }
+
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn index(mut self, index: u64) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamEvent {
+    #[serde(rename = "Topic")]
+    pub topic: Topic,
+    /// e.g. `JobRegistered`, `AllocationUpdated` or `EvaluationUpdated`.
+    #[serde(rename = "Type")]
+    pub type_field: String,
+    #[serde(rename = "Key")]
+    pub key: String,
+    #[serde(rename = "Namespace")]
+    #[serde(default)]
+    pub namespace: String,
+    #[serde(rename = "FilterKeys")]
+    #[serde(default)]
+    pub filter_keys: Option<Vec<String>>,
+    #[serde(rename = "Index")]
+    pub index: u64,
+    #[serde(rename = "Payload")]
+    pub payload: EventPayload,
+}
+
+/// Object carried by a [`StreamEvent`]. Allocations are sent without their job.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum EventPayload {
+    Job(Box<Job>),
+    Allocation(Box<Allocation>),
+    Evaluation(Box<Evaluation>),
+    Deployment(serde_json::Value),
+    Node(Box<Node>),
+    NodePool(serde_json::Value),
+    Service(serde_json::Value),
+    #[serde(rename = "ACLToken")]
+    AclToken(serde_json::Value),
+    #[serde(rename = "ACLPolicy")]
+    AclPolicy(serde_json::Value),
+    #[serde(rename = "ACLRole")]
+    AclRole(serde_json::Value),
+    /// Payload of a type this crate does not know about, e.g. `{"Variable": {..}}`, kept as
+    /// sent.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for EventPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(PayloadVisitor)
+    }
+}
+
+/// Decodes known payloads in place, so their errors keep the path to the offending field.
+struct PayloadVisitor;
+
+impl<'de> Visitor<'de> for PayloadVisitor {
+    type Value = EventPayload;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an event payload")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let kind: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let payload = match kind.as_str() {
+            "Job" => EventPayload::Job(map.next_value()?),
+            "Allocation" => EventPayload::Allocation(map.next_value()?),
+            "Evaluation" => EventPayload::Evaluation(map.next_value()?),
+            "Deployment" => EventPayload::Deployment(map.next_value()?),
+            "Node" => EventPayload::Node(map.next_value()?),
+            "NodePool" => EventPayload::NodePool(map.next_value()?),
+            "Service" => EventPayload::Service(map.next_value()?),
+            "ACLToken" => EventPayload::AclToken(map.next_value()?),
+            "ACLPolicy" => EventPayload::AclPolicy(map.next_value()?),
+            "ACLRole" => EventPayload::AclRole(map.next_value()?),
+            _ => {
+                let mut other = serde_json::Map::new();
+                other.insert(kind, map.next_value()?);
+                while let Some((key, value)) = map.next_entry()? {
+                    other.insert(key, value);
+                }
+                return Ok(EventPayload::Other(serde_json::Value::Object(other)));
+            }
+        };
+        while map
+            .next_entry::<de::IgnoredAny, de::IgnoredAny>()?
+            .is_some()
+        {}
+        Ok(payload)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventStreamItem {
+    /// Events committed at `index`.
+    Events {
+        index: u64,
+        events: Vec<StreamEvent>,
+    },
+    /// The stream reconnected and resumed at `resumed_index` after last delivering
+    /// `last_index`, so events in between may have been dropped from Nomad's buffer.
+    ///
+    /// This only means "may have missed events". Raft indexes are shared by every topic and
+    /// most indexes produce no event matching the topic filter, so the stream usually resumes
+    /// further than `last_index + 1` without anything missing. Callers that must not miss an
+    /// event should re-read the state they track when they see a gap.
+    Gap { last_index: u64, resumed_index: u64 },
+}
+
+#[derive(Deserialize)]
+struct EventFrame {
+    #[serde(rename = "Index")]
+    #[serde(default)]
+    index: u64,
+    #[serde(rename = "Events")]
+    #[serde(default)]
+    events: Option<Vec<StreamEvent>>,
+}
+
+#[derive(Deserialize)]
+struct FrameIndex {
+    #[serde(rename = "Index")]
+    #[serde(default)]
+    index: u64,
+}
+
+struct EventStreamState {
+    client: NomadClient,
+    options: EventStreamOptions,
+    body: Option<BoxStream<'static, reqwest::Result<Bytes>>>,
+    buf: Vec<u8>,
+    pending: VecDeque<Result<EventStreamItem, NomadError>>,
+    last_index: u64,
+    reconnected: bool,
+    failures: u32,
+    done: bool,
+}
+
+impl NomadClient {
+    /// https://www.nomadproject.io/api-docs/events#event-stream
+    /// Method	Path	Produces
+    /// GET	/v1/event/stream	application/x-ndjson
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	management, or namespace:read-job and node:read depending on the topics
+    ///
+    /// Connection failures are retried with an exponential backoff. ACL denials and other
+    /// non-retryable errors are yielded and end the stream. A frame that cannot be decoded is
+    /// yielded as a [`NomadError::Deserialization`] and the stream continues with the next one.
+    pub fn event_stream(
+        &self,
+        options: &EventStreamOptions,
+    ) -> BoxStream<'static, Result<EventStreamItem, NomadError>> {
+        let state = EventStreamState {
+            client: self.clone(),
+            options: options.clone(),
+            body: None,
+            buf: Vec::new(),
+            pending: VecDeque::new(),
+            last_index: 0,
+            reconnected: false,
+            failures: 0,
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.pending.pop_front() {
+                    return Some((item, state));
+                }
+                if state.done {
+                    return None;
+                }
+                match state.body.as_mut() {
+                    None => {
+                        if state.failures > 0 {
+                            tokio::time::sleep(backoff(state.failures)).await;
+                        }
+                        let from = if state.last_index > 0 {
+                            Some(state.last_index + 1)
+                        } else {
+                            state.options.index
+                        };
+                        match connect(&state.client, &state.options, from).await {
+                            Ok(body) => {
+                                state.body = Some(body);
+                                state.buf.clear();
+                            }
+                            Err(e) => {
+                                if e.is_retryable() {
+                                    state.failures += 1;
+                                    warn!("Event stream connection failed: {}", e);
+                                } else {
+                                    state.done = true;
+                                }
+                                return Some((Err(e), state));
+                            }
+                        }
+                    }
+                    Some(body) => {
+                        match tokio::time::timeout(HEARTBEAT_TIMEOUT, body.next()).await {
+                            Ok(Some(Ok(chunk))) => {
+                                state.buf.extend_from_slice(&chunk);
+                                state.drain_lines();
+                            }
+                            Ok(Some(Err(e))) => {
+                                warn!("Event stream interrupted: {}", e);
+                                state.disconnect();
+                            }
+                            Ok(None) => {
+                                debug!("Event stream closed by the server");
+                                state.disconnect();
+                            }
+                            Err(_) => {
+                                warn!("No heartbeat for {:?}, reconnecting", HEARTBEAT_TIMEOUT);
+                                state.disconnect();
+                            }
+                        }
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+async fn connect(
+    client: &NomadClient,
+    options: &EventStreamOptions,
+    index: Option<u64>,
+) -> Result<BoxStream<'static, reqwest::Result<Bytes>>, NomadError> {
+    let url = format!("{}/v1/event/stream", client.base_url());
+    let q = QueryOptions {
+        namespace: options.namespace.clone(),
+        index,
+        ..QueryOptions::default()
+    };
+    let topics: Vec<(&str, String)> = options
+        .topics
+        .iter()
+        .map(|(topic, key)| ("topic", format!("{}:{}", topic, key)))
+        .collect();
+    debug!("Connecting event stream from index {:?}", index);
+    let response = client
+        .execute(client.query(&url, &q).query(&topics))
+        .await?;
+    Ok(response.bytes_stream().boxed())
+}
+
+impl EventStreamState {
+    fn disconnect(&mut self) {
+        self.body = None;
+        self.reconnected = true;
+        self.failures += 1;
+    }
+
+    /// Decodes every complete line in the buffer.
+    fn drain_lines(&mut self) {
+        while let Some(end) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=end).collect();
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            // Anything received means the connection is healthy again.
+            self.failures = 0;
+            match crate::client::decode::<EventFrame>(&line) {
+                Ok(EventFrame { events: None, .. }) => {}
+                Ok(EventFrame {
+                    index,
+                    events: Some(events),
+                }) => {
+                    self.check_gap(index);
+                    self.pending
+                        .push_back(Ok(EventStreamItem::Events { index, events }));
+                }
+                Err(e) => {
+                    if let Ok(frame) = serde_json::from_slice::<FrameIndex>(&line) {
+                        self.check_gap(frame.index);
+                    }
+                    self.pending.push_back(Err(e));
+                }
+            }
+        }
+    }
+
+    fn check_gap(&mut self, index: u64) {
+        if self.reconnected && self.last_index > 0 && index > self.last_index + 1 {
+            self.pending.push_back(Ok(EventStreamItem::Gap {
+                last_index: self.last_index,
+                resumed_index: index,
+            }));
+        }
+        self.reconnected = false;
+        self.last_index = self.last_index.max(index);
+    }
+}
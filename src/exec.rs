@@ -0,0 +1,365 @@
+//! Client for `alloc exec` sessions.
+//!
+//! https://www.nomadproject.io/api-docs/allocations#exec-allocation
+//!
+//! The session runs over a WebSocket exchanging JSON frames. It is driven by a background task
+//! on the current Tokio runtime, so reading stdout does not have to be interleaved with writing
+//! stdin. Output nobody reads is buffered until the session is dropped.
+//!
+//! ```no_run
+//! # async fn exec(client: nomad_api::client::NomadClient) -> Result<(), nomad_api::error::NomadError> {
+//! use futures::StreamExt;
+//!
+//! let mut session = client
+//!     .exec("5456bd7a", "worker", &["nvidia-smi".to_string()], false)
+//!     .await?;
+//! session.stdin.close().await?;
+//! while let Some(chunk) = session.stdout.next().await {
+//!     print!("{}", String::from_utf8_lossy(&chunk));
+//! }
+//! let exit_code = session.wait().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::NomadClient;
+use crate::error::NomadError;
+use bytes::Bytes;
+use futures::channel::{mpsc, oneshot};
+use futures::stream::{self, BoxStream, StreamExt};
+use futures::{Future, SinkExt};
+use log::{debug, trace, warn};
+use reqwest::{Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::Connector;
+
+/// Interval of the heartbeats keeping idle sessions open, the same as the Nomad CLI.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Running `alloc exec` session.
+///
+/// The fields can be moved out to use them from different tasks.
+pub struct ExecSession {
+    pub stdin: ExecStdin,
+    pub stdout: BoxStream<'static, Bytes>,
+    pub stderr: BoxStream<'static, Bytes>,
+    pub exit: ExecExit,
+}
+
+impl ExecSession {
+    /// Resizes the terminal of a session started with `tty`.
+    pub async fn resize(&mut self, width: u16, height: u16) -> Result<(), NomadError> {
+        self.stdin.resize(width, height).await
+    }
+
+    /// Waits for the command to exit and returns its exit code.
+    pub async fn wait(self) -> Result<i32, NomadError> {
+        self.exit.await
+    }
+}
+
+/// Writer for the stdin of the command. Dropping it closes stdin.
+pub struct ExecStdin {
+    input: mpsc::Sender<ExecInput>,
+}
+
+impl ExecStdin {
+    pub async fn write(&mut self, data: &[u8]) -> Result<(), NomadError> {
+        self.send(ExecInput {
+            stdin: Some(ExecData {
+                data: data.to_vec(),
+                close: false,
+            }),
+            ..ExecInput::default()
+        })
+        .await
+    }
+
+    /// Closes stdin, signalling end of input to the command.
+    pub async fn close(&mut self) -> Result<(), NomadError> {
+        self.send(ExecInput {
+            stdin: Some(ExecData {
+                data: Vec::new(),
+                close: true,
+            }),
+            ..ExecInput::default()
+        })
+        .await
+    }
+
+    /// Resizes the terminal of a session started with `tty`.
+    pub async fn resize(&mut self, width: u16, height: u16) -> Result<(), NomadError> {
+        self.send(ExecInput {
+            tty_size: Some(TerminalSize { width, height }),
+            ..ExecInput::default()
+        })
+        .await
+    }
+
+    async fn send(&mut self, input: ExecInput) -> Result<(), NomadError> {
+        self.input
+            .send(input)
+            .await
+            .map_err(|_| NomadError::from(tungstenite::Error::AlreadyClosed))
+    }
+}
+
+/// Exit code of the command, resolved when the session ends.
+pub struct ExecExit {
+    exit: oneshot::Receiver<Result<i32, NomadError>>,
+}
+
+impl Future for ExecExit {
+    type Output = Result<i32, NomadError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.exit).poll(cx).map(|result| {
+            result.unwrap_or(Err(NomadError::from(tungstenite::Error::ConnectionClosed)))
+        })
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ExecInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stdin: Option<ExecData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tty_size: Option<TerminalSize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ExecData {
+    #[serde(default, with = "crate::fs::base64_bytes")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data: Vec<u8>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    close: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct TerminalSize {
+    width: u16,
+    height: u16,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecOutput {
+    #[serde(default)]
+    stdout: Option<ExecData>,
+    #[serde(default)]
+    stderr: Option<ExecData>,
+    #[serde(default)]
+    exited: bool,
+    #[serde(default)]
+    result: Option<ExecResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecResult {
+    #[serde(default)]
+    exit_code: i32,
+}
+
+impl NomadClient {
+    /// https://www.nomadproject.io/api-docs/allocations#exec-allocation
+    /// This endpoint executes a command inside the isolation context of a task.
+    /// Method	Path	Produces
+    /// WebSocket	/v1/client/allocation/:alloc_id/exec	WebSocket JSON streams
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:alloc-exec (and namespace:alloc-node-exec if the task uses a raw_exec driver)
+    ///
+    /// `tty` allocates a pseudo terminal, merging stderr into stdout.
+    pub async fn exec(
+        &self,
+        alloc_id: &str,
+        task: &str,
+        command: &[String],
+        tty: bool,
+    ) -> Result<ExecSession, NomadError> {
+        let url = format!("{}/v1/client/allocation/{}/exec", self.base_url(), alloc_id);
+        trace!("Exec call to {}", &url);
+        let command = serde_json::to_string(command).expect("strings always serialize");
+        let request = self
+            .request(Method::GET, &url)
+            .query(&[
+                ("task", task.to_string()),
+                ("tty", tty.to_string()),
+                ("command", command),
+            ])
+            .build()?;
+
+        let mut url: Url = request.url().clone();
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme)
+            .map_err(|_| NomadError::Config(format!("invalid address {}", self.base_url())))?;
+        let mut ws_request = url
+            .as_str()
+            .into_client_request()
+            .map_err(NomadError::from)?;
+        ws_request.headers_mut().extend(request.headers().clone());
+        let connector = self.tls_config().map(Connector::Rustls);
+        let (socket, _) =
+            tokio_tungstenite::connect_async_tls_with_config(ws_request, None, false, connector)
+                .await
+                .map_err(|e| match e {
+                    tungstenite::Error::Http(response) => NomadError::from_status(
+                        StatusCode::from_u16(response.status().as_u16())
+                            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                        String::from_utf8_lossy(response.body().as_deref().unwrap_or_default())
+                            .into_owned(),
+                    ),
+                    e => NomadError::from(e),
+                })?;
+        debug!("Exec session started in {}", alloc_id);
+
+        let (input_tx, input_rx) = mpsc::channel(16);
+        let (stdout_tx, stdout_rx) = mpsc::unbounded();
+        let (stderr_tx, stderr_rx) = mpsc::unbounded();
+        let (exit_tx, exit_rx) = oneshot::channel();
+        let session = ExecDriver {
+            stdout: stdout_tx,
+            stderr: stderr_tx,
+        };
+        tokio::spawn(async move {
+            let result = session.run(socket, input_rx).await;
+            let _ = exit_tx.send(result);
+        });
+
+        Ok(ExecSession {
+            stdin: ExecStdin { input: input_tx },
+            stdout: stdout_rx.boxed(),
+            stderr: stderr_rx.boxed(),
+            exit: ExecExit { exit: exit_rx },
+        })
+    }
+}
+
+enum Event {
+    Received(Result<Message, tungstenite::Error>),
+    Input(ExecInput),
+    InputClosed,
+    Heartbeat,
+}
+
+struct ExecDriver {
+    stdout: mpsc::UnboundedSender<Bytes>,
+    stderr: mpsc::UnboundedSender<Bytes>,
+}
+
+impl ExecDriver {
+    /// Relays frames until the command exits, returning its exit code.
+    async fn run<S>(
+        self,
+        socket: tokio_tungstenite::WebSocketStream<S>,
+        input: mpsc::Receiver<ExecInput>,
+    ) -> Result<i32, NomadError>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, received) = socket.split();
+        let heartbeats = stream::unfold((), |()| async {
+            tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+            Some((Event::Heartbeat, ()))
+        })
+        .boxed();
+        let input = input
+            .map(Event::Input)
+            .chain(stream::once(async { Event::InputClosed }))
+            .boxed();
+        let mut events = stream::select(
+            received.map(Event::Received),
+            stream::select(input, heartbeats),
+        );
+
+        while let Some(event) = events.next().await {
+            let input = match event {
+                Event::Received(Ok(Message::Text(text))) => {
+                    if let Some(code) = self.relay(text.as_bytes())? {
+                        let _ = sink.close().await;
+                        return Ok(code);
+                    }
+                    continue;
+                }
+                Event::Received(Ok(Message::Binary(data))) => {
+                    if let Some(code) = self.relay(&data)? {
+                        let _ = sink.close().await;
+                        return Ok(code);
+                    }
+                    continue;
+                }
+                Event::Received(Ok(Message::Close(frame))) => return Err(closed(frame)),
+                Event::Received(Ok(_)) => continue,
+                Event::Received(Err(e)) => return Err(e.into()),
+                Event::Input(input) => input,
+                Event::InputClosed => ExecInput {
+                    stdin: Some(ExecData {
+                        data: Vec::new(),
+                        close: true,
+                    }),
+                    ..ExecInput::default()
+                },
+                Event::Heartbeat => ExecInput::default(),
+            };
+            let frame = serde_json::to_string(&input).expect("exec input always serializes");
+            sink.send(Message::Text(frame))
+                .await
+                .map_err(NomadError::from)?;
+        }
+        Err(NomadError::from(tungstenite::Error::ConnectionClosed))
+    }
+
+    /// Forwards the output carried by a frame, returning the exit code once the command exited.
+    fn relay(&self, frame: &[u8]) -> Result<Option<i32>, NomadError> {
+        let output: ExecOutput = crate::client::decode(frame)?;
+        if let Some(stdout) = output.stdout {
+            if !stdout.data.is_empty() {
+                let _ = self.stdout.unbounded_send(stdout.data.into());
+            }
+            if stdout.close {
+                self.stdout.close_channel();
+            }
+        }
+        if let Some(stderr) = output.stderr {
+            if !stderr.data.is_empty() {
+                let _ = self.stderr.unbounded_send(stderr.data.into());
+            }
+            if stderr.close {
+                self.stderr.close_channel();
+            }
+        }
+        if output.exited {
+            return Ok(Some(output.result.map_or(0, |result| result.exit_code)));
+        }
+        Ok(None)
+    }
+}
+
+/// Nomad reports failures by closing the socket with `4000 + HTTP status` and the error as
+/// reason, e.g. `4404` with "task not found".
+fn closed(frame: Option<CloseFrame<'_>>) -> NomadError {
+    let frame = match frame {
+        Some(frame) if frame.code != CloseCode::Normal => frame,
+        _ => return NomadError::from(tungstenite::Error::ConnectionClosed),
+    };
+    let code = u16::from(frame.code);
+    warn!("Exec session closed with {}: {}", code, frame.reason);
+    match code
+        .checked_sub(4000)
+        .and_then(|status| StatusCode::from_u16(status).ok())
+    {
+        Some(status) => NomadError::from_status(status, frame.reason.into_owned()),
+        None => NomadError::Http {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            body: frame.reason.into_owned(),
+        },
+    }
+}
@@ -0,0 +1,410 @@
+//! Client for the allocation file system endpoints.
+//!
+//! https://www.nomadproject.io/api-docs/client#read-file
+//!
+//! Paths are relative to the allocation directory, e.g. `alloc/data/result.parquet` or
+//! `worker/local/config.json`.
+//!
+//! Streaming endpoints answer with a sequence of JSON [`StreamFrame`]s carrying base64 encoded
+//! data. Frames are decoded as they arrive, heartbeat frames are skipped.
+
+use crate::client::NomadClient;
+use crate::error::NomadError;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt};
+use log::trace;
+use reqwest::Method;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogType {
+    Stdout,
+    Stderr,
+}
+
+impl fmt::Display for LogType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LogType::Stdout => "stdout",
+            LogType::Stderr => "stderr",
+        })
+    }
+}
+
+/// Where the `offset` of a stream is counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    Start,
+    End,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Origin::Start => "start",
+            Origin::End => "end",
+        })
+    }
+}
+
+/// Change to the streamed file reported by Nomad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileEvent {
+    /// The file shrank, streaming restarted from its beginning.
+    Truncated,
+    /// The file was removed, e.g. rotated by the log collector. Logs continue in the next file.
+    Deleted,
+    Other(String),
+}
+
+impl Serialize for FileEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            FileEvent::Truncated => "file truncated",
+            FileEvent::Deleted => "file deleted",
+            FileEvent::Other(event) => event,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for FileEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let event = String::deserialize(deserializer)?;
+        Ok(match event.as_str() {
+            "file truncated" => FileEvent::Truncated,
+            "file deleted" => FileEvent::Deleted,
+            _ => FileEvent::Other(event),
+        })
+    }
+}
+
+/// https://www.nomadproject.io/api-docs/client#stat-file
+/// Method	Path	Produces
+/// GET	/v1/client/fs/stat/:alloc_id	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocFileInfo {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "IsDir")]
+    pub is_dir: bool,
+    /// Size in bytes.
+    #[serde(rename = "Size")]
+    pub size: i64,
+    /// Unix permissions, e.g. `-rw-r--r--`.
+    #[serde(rename = "FileMode")]
+    pub file_mode: String,
+    /// RFC 3339 time of the last modification.
+    #[serde(rename = "ModTime")]
+    pub mod_time: String,
+    /// MIME type detected by Nomad, e.g. `application/octet-stream`.
+    #[serde(rename = "ContentType")]
+    #[serde(default)]
+    pub content_type: String,
+}
+
+/// Frame sent by the streaming file system endpoints.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamFrame {
+    /// Offset in `file` after this frame, pass it as offset to resume the stream.
+    #[serde(rename = "Offset")]
+    #[serde(default)]
+    pub offset: i64,
+    /// Decoded bytes.
+    #[serde(rename = "Data")]
+    #[serde(default, with = "base64_bytes")]
+    pub data: Vec<u8>,
+    /// Path of the file the data was read from, e.g. `alloc/logs/worker.stdout.0`.
+    #[serde(rename = "File")]
+    #[serde(default)]
+    pub file: String,
+    #[serde(rename = "FileEvent")]
+    #[serde(default)]
+    pub file_event: Option<FileEvent>,
+}
+
+impl StreamFrame {
+    fn is_heartbeat(&self) -> bool {
+        self.data.is_empty() && self.file.is_empty() && self.file_event.is_none()
+    }
+}
+
+pub(crate) mod base64_bytes {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(data) => STANDARD.decode(data).map_err(de::Error::custom),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl NomadClient {
+    /// https://www.nomadproject.io/api-docs/client#stream-logs
+    /// This endpoint streams a task's stderr/stdout logs.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/logs/:alloc_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-logs
+    ///
+    /// With `follow` the stream stays open and yields new output as the task writes it,
+    /// otherwise it ends with the current end of the logs. `offset` is counted in bytes from
+    /// `origin`, e.g. `Origin::End` with an offset of 4096 tails the last 4 KiB.
+    pub async fn stream_logs(
+        &self,
+        alloc_id: &str,
+        task: &str,
+        log_type: LogType,
+        follow: bool,
+        origin: Origin,
+        offset: i64,
+    ) -> Result<BoxStream<'static, Result<StreamFrame, NomadError>>, NomadError> {
+        let body = self
+            .logs(alloc_id, task, log_type, follow, origin, offset, false)
+            .await?;
+        Ok(frames(body))
+    }
+
+    /// Same as [`NomadClient::stream_logs`] in plain mode: Nomad sends the raw log bytes
+    /// without framing, so file events and offsets are not reported.
+    pub async fn stream_logs_plain(
+        &self,
+        alloc_id: &str,
+        task: &str,
+        log_type: LogType,
+        follow: bool,
+        origin: Origin,
+        offset: i64,
+    ) -> Result<BoxStream<'static, Result<Bytes, NomadError>>, NomadError> {
+        let body = self
+            .logs(alloc_id, task, log_type, follow, origin, offset, true)
+            .await?;
+        Ok(body.map(|chunk| chunk.map_err(NomadError::from)).boxed())
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#list-files
+    /// This endpoint lists files in an allocation directory.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/ls/:alloc_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-fs
+    pub async fn list_allocation_files(
+        &self,
+        alloc_id: &str,
+        path: &str,
+    ) -> Result<Vec<AllocFileInfo>, NomadError> {
+        let url = format!("{}/v1/client/fs/ls/{}", self.base_url(), alloc_id);
+        trace!("ListAllocationFiles call to {}", &url);
+        self.send(self.request(Method::GET, &url).query(&[("path", path)]))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#stat-file
+    /// This endpoint stats a file in an allocation.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/stat/:alloc_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-fs
+    pub async fn stat_allocation_file(
+        &self,
+        alloc_id: &str,
+        path: &str,
+    ) -> Result<AllocFileInfo, NomadError> {
+        let url = format!("{}/v1/client/fs/stat/{}", self.base_url(), alloc_id);
+        trace!("StatAllocationFile call to {}", &url);
+        self.send(self.request(Method::GET, &url).query(&[("path", path)]))
+            .await
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#read-file
+    /// This endpoint reads the contents of a file in an allocation directory.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/cat/:alloc_id	text/plain
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-fs
+    pub async fn read_allocation_file(
+        &self,
+        alloc_id: &str,
+        path: &str,
+    ) -> Result<Bytes, NomadError> {
+        let url = format!("{}/v1/client/fs/cat/{}", self.base_url(), alloc_id);
+        trace!("ReadAllocationFile call to {}", &url);
+        let response = self
+            .execute(self.request(Method::GET, &url).query(&[("path", path)]))
+            .await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#read-file-at-offset
+    /// This endpoint reads the contents of a file in an allocation directory at a particular
+    /// offset and limit.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/readat/:alloc_id	text/plain
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-fs
+    pub async fn read_allocation_file_at(
+        &self,
+        alloc_id: &str,
+        path: &str,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Bytes, NomadError> {
+        let url = format!("{}/v1/client/fs/readat/{}", self.base_url(), alloc_id);
+        trace!("ReadAllocationFileAt call to {}", &url);
+        let request = self.request(Method::GET, &url).query(&[
+            ("path", path.to_string()),
+            ("offset", offset.to_string()),
+            ("limit", limit.to_string()),
+        ]);
+        let response = self.execute(request).await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// https://www.nomadproject.io/api-docs/client#stream-file
+    /// This endpoint streams the contents of a file in an allocation directory.
+    /// Method	Path	Produces
+    /// GET	/v1/client/fs/stream/:alloc_id	application/json
+    ///
+    /// Blocking Queries	ACL Required
+    /// NO	namespace:read-fs
+    ///
+    /// With `follow` the stream stays open and yields data appended to the file, otherwise it
+    /// ends at the current end of the file.
+    pub async fn stream_allocation_file(
+        &self,
+        alloc_id: &str,
+        path: &str,
+        follow: bool,
+        origin: Origin,
+        offset: i64,
+    ) -> Result<BoxStream<'static, Result<StreamFrame, NomadError>>, NomadError> {
+        let url = format!("{}/v1/client/fs/stream/{}", self.base_url(), alloc_id);
+        trace!("StreamAllocationFile call to {}", &url);
+        let request = self.request(Method::GET, &url).query(&[
+            ("path", path.to_string()),
+            ("follow", follow.to_string()),
+            ("origin", origin.to_string()),
+            ("offset", offset.to_string()),
+        ]);
+        let response = self.execute(request).await?;
+        Ok(frames(response.bytes_stream().boxed()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn logs(
+        &self,
+        alloc_id: &str,
+        task: &str,
+        log_type: LogType,
+        follow: bool,
+        origin: Origin,
+        offset: i64,
+        plain: bool,
+    ) -> Result<BoxStream<'static, reqwest::Result<Bytes>>, NomadError> {
+        let url = format!("{}/v1/client/fs/logs/{}", self.base_url(), alloc_id);
+        trace!("StreamLogs call to {}", &url);
+        let request = self.request(Method::GET, &url).query(&[
+            ("task", task.to_string()),
+            ("type", log_type.to_string()),
+            ("follow", follow.to_string()),
+            ("origin", origin.to_string()),
+            ("offset", offset.to_string()),
+            ("plain", plain.to_string()),
+        ]);
+        let response = self.execute(request).await?;
+        Ok(response.bytes_stream().boxed())
+    }
+}
+
+struct FrameState {
+    body: BoxStream<'static, reqwest::Result<Bytes>>,
+    buf: Vec<u8>,
+    pending: VecDeque<Result<StreamFrame, NomadError>>,
+    done: bool,
+}
+
+/// Decodes the concatenated JSON frames of a streaming body.
+pub(crate) fn frames(
+    body: BoxStream<'static, reqwest::Result<Bytes>>,
+) -> BoxStream<'static, Result<StreamFrame, NomadError>> {
+    let state = FrameState {
+        body,
+        buf: Vec::new(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.body.next().await {
+                Some(Ok(chunk)) => {
+                    state.buf.extend_from_slice(&chunk);
+                    state.drain_frames();
+                }
+                Some(Err(e)) => {
+                    state.done = true;
+                    return Some((Err(e.into()), state));
+                }
+                None => {
+                    state.done = true;
+                    if !state.buf.iter().all(u8::is_ascii_whitespace) {
+                        let e = crate::client::decode::<StreamFrame>(&state.buf).err();
+                        state.pending.extend(e.map(Err));
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+impl FrameState {
+    /// Decodes every complete frame in the buffer, keeping a trailing partial frame.
+    fn drain_frames(&mut self) {
+        let mut frames = serde_json::Deserializer::from_slice(&self.buf).into_iter::<StreamFrame>();
+        let mut consumed = 0;
+        let mut failed = None;
+        while let Some(frame) = frames.next() {
+            match frame {
+                Ok(frame) => {
+                    consumed = frames.byte_offset();
+                    if !frame.is_heartbeat() {
+                        self.pending.push_back(Ok(frame));
+                    }
+                }
+                Err(e) if e.is_eof() => break,
+                Err(_) => {
+                    failed = Some(consumed);
+                    break;
+                }
+            }
+        }
+        if let Some(start) = failed {
+            // A malformed frame cannot be resynchronised, report it and end the stream.
+            let e = crate::client::decode::<StreamFrame>(&self.buf[start..]).err();
+            self.pending.extend(e.map(Err));
+            self.done = true;
+            self.buf.clear();
+            return;
+        }
+        self.buf.drain(..consumed);
+    }
+}
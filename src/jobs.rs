@@ -1,11 +1,16 @@
 #![allow(clippy::tabs_in_doc_comments)]
 
+use crate::constraints::{Affinity, Constraint, Spread};
+use crate::evaluations::Evaluation;
+use crate::services::Service;
+use crate::status::{AllocClientStatus, AllocDesiredStatus, JobStatus, TaskStateKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::Hash;
 #[allow(clippy::tabs_in_doc_comments)]
 /// Create mostly with https://transform.tools/json-to-rust-serde
-
+#[deprecated(note = "use JobStatus, AllocClientStatus or TaskStateKind from crate::status")]
 #[derive(Debug, Eq, PartialEq, Hash, Deserialize, Serialize, Clone)]
 pub enum RunningStatus {
     #[serde(alias = "queued")]
@@ -28,12 +33,16 @@ pub enum RunningStatus {
     Missing,
 }
 
+#[allow(deprecated, clippy::derivable_impls)]
 impl Default for RunningStatus {
     fn default() -> Self {
         RunningStatus::Pending
     }
 }
 
+/// Largest payload, before base64 encoding, Nomad accepts for a dispatch.
+pub const DISPATCH_PAYLOAD_LIMIT: usize = 16 * 1024;
+
 /// https://www.nomadproject.io/api-docs/jobs#dispatch-job
 /// Method	Path                    Produces
 /// POST	/v1/job/:job_id/dispatch	application/json
@@ -53,12 +62,12 @@ pub struct DispatchJobRequest {
 }
 
 /// Sample Response:
-/// ```
+/// ```json
 /// {
 ///   "Index": 13,
 ///   "JobCreateIndex": 12,
 ///   "EvalCreateIndex": 13,
-///   "eval_id": "e5f55fac-bc69-119d-528a-1fc7ade5e02c",
+///   "EvalID": "e5f55fac-bc69-119d-528a-1fc7ade5e02c",
 ///   "DispatchedJobID": "example/dispatch-1485408778-81644024"
 /// }
 /// ```
@@ -70,7 +79,7 @@ pub struct DispatchJobResponse {
     pub job_create_index: Option<u32>,
     #[serde(rename = "EvalCreateIndex")]
     pub eval_create_index: Option<u32>,
-    #[serde(rename = "eval_id")]
+    #[serde(rename = "EvalID", alias = "eval_id")]
     pub eval_id: Option<String>,
     #[serde(rename = "DispatchedJobID")]
     pub dispatched_job_id: String,
@@ -85,12 +94,80 @@ pub struct JobStopResponse {
     pub job_modify_index: u32,
 }
 
-/// https://www.nomadproject.io/api-docs/jobs#list-job-allocations
+/// https://www.nomadproject.io/api-docs/allocations#stop-allocation
+/// Method	Path	Produces
+/// POST	/v1/allocation/:alloc_id/stop	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocStopResponse {
+    #[serde(rename = "EvalID")]
+    pub eval_id: String,
+    #[serde(rename = "Index")]
+    pub index: u64,
+}
+
+/// https://www.nomadproject.io/api-docs/allocations#restart-allocation
+/// Method	Path	Produces
+/// POST	/v1/client/allocation/:alloc_id/restart	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocRestartRequest {
+    #[serde(rename = "TaskName")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub task_name: String,
+    /// Also restarts lifecycle tasks that already completed, e.g. prestart and poststop tasks.
+    #[serde(rename = "AllTasks")]
+    pub all_tasks: bool,
+}
+
+/// https://www.nomadproject.io/api-docs/allocations#signal-allocation
+/// Method	Path	Produces
+/// POST	/v1/client/allocation/:alloc_id/signal	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocSignalRequest {
+    #[serde(rename = "Signal")]
+    pub signal: String,
+    #[serde(rename = "Task")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub task: String,
+}
+
+/// Answer to [`AllocRestartRequest`] and [`AllocSignalRequest`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocLifecycleResponse {
+    /// Raft index of the change, 0 when the client handled it without a write.
+    #[serde(rename = "Index")]
+    #[serde(default)]
+    pub index: u64,
+}
+
+/// Endpoint specific parameters of [`NomadClient::list_allocations`].
+///
+/// [`NomadClient::list_allocations`]: crate::client::NomadClient::list_allocations
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocationListOptions {
+    /// Includes the `AllocatedResources` of every allocation.
+    pub resources: bool,
+    /// Includes the `TaskStates` of every allocation, skip them to shrink large lists.
+    pub task_states: bool,
+}
+
+impl Default for AllocationListOptions {
+    fn default() -> Self {
+        AllocationListOptions {
+            resources: false,
+            task_states: true,
+        }
+    }
+}
+
+/// https://www.nomadproject.io/api-docs/allocations#list-allocations
 /// Method	Path                        Produces
+/// GET    /v1/allocations    application/json
 /// GET    /v1/job/:job_id/allocations    application/json
+///
+/// `AllocatedResources` is only set when the list is requested with `resources=true`.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ListJobAllocationsResponse {
+pub struct AllocationListStub {
     #[serde(rename = "ID")]
     pub id: String,
     #[serde(rename = "EvalID")]
@@ -112,13 +189,14 @@ pub struct ListJobAllocationsResponse {
     #[serde(rename = "TaskGroup")]
     pub task_group: String,
     #[serde(rename = "AllocatedResources")]
+    #[serde(default)]
     pub allocated_resources: ::serde_json::Value,
     #[serde(rename = "DesiredStatus")]
-    pub desired_status: String,
+    pub desired_status: AllocDesiredStatus,
     #[serde(rename = "DesiredDescription")]
     pub desired_description: String,
     #[serde(rename = "ClientStatus")]
-    pub client_status: String,
+    pub client_status: AllocClientStatus,
     #[serde(rename = "ClientDescription")]
     pub client_description: Option<String>,
     #[serde(rename = "DesiredTransition")]
@@ -145,6 +223,9 @@ pub struct ListJobAllocationsResponse {
     pub modify_time: i64,
 }
 
+#[deprecated(note = "renamed to `AllocationListStub`")]
+pub type ListJobAllocationsResponse = AllocationListStub;
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DesiredTransition {
@@ -203,7 +284,7 @@ pub struct Allocation {
     #[serde(rename = "ClientDescription")]
     pub client_description: Option<String>,
     #[serde(rename = "ClientStatus")]
-    pub client_status: RunningStatus,
+    pub client_status: AllocClientStatus,
     #[serde(rename = "CreateIndex")]
     pub create_index: i64,
     #[serde(rename = "CreateTime")]
@@ -211,15 +292,16 @@ pub struct Allocation {
     #[serde(rename = "DeploymentID")]
     pub deployment_id: Option<String>,
     #[serde(rename = "DeploymentStatus")]
-    pub deployment_status: Option<DeploymentStatus>,
+    pub deployment_status: Option<AllocDeploymentStatus>,
     #[serde(rename = "DesiredStatus")]
-    pub desired_status: String,
+    pub desired_status: AllocDesiredStatus,
     #[serde(rename = "EvalID")]
     pub eval_id: Option<String>,
     #[serde(rename = "ID")]
     pub id: String,
+    /// `None` for allocations of the event stream, which Nomad sends without their job.
     #[serde(rename = "Job")]
-    pub job: Job,
+    pub job: Option<Job>,
     #[serde(rename = "JobID")]
     pub job_id: String,
     #[serde(rename = "Metrics")]
@@ -361,7 +443,7 @@ pub struct Memory {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct DeploymentStatus {
+pub struct AllocDeploymentStatus {
     #[serde(rename = "Canary")]
     pub canary: bool,
     #[serde(rename = "Healthy")]
@@ -376,11 +458,11 @@ pub struct DeploymentStatus {
 #[serde(rename_all = "camelCase")]
 pub struct Job {
     #[serde(rename = "Affinities")]
-    pub affinities: Option<::serde_json::Value>,
+    pub affinities: Option<Vec<Affinity>>,
     #[serde(rename = "AllAtOnce")]
     pub all_at_once: Option<bool>,
     #[serde(rename = "Constraints")]
-    pub constraints: Option<::serde_json::Value>,
+    pub constraints: Option<Vec<Constraint>>,
     #[serde(rename = "ConsulNamespace")]
     pub consul_namespace: Option<String>,
     #[serde(rename = "ConsulToken")]
@@ -409,7 +491,7 @@ pub struct Job {
     #[serde(rename = "NomadTokenID")]
     pub nomad_token_id: Option<String>,
     #[serde(rename = "ParameterizedJob")]
-    pub parameterized_job: Option<::serde_json::Value>,
+    pub parameterized_job: Option<ParameterizedJobConfig>,
     #[serde(rename = "ParentID")]
     pub parent_id: Option<String>,
     #[serde(rename = "Payload")]
@@ -421,11 +503,11 @@ pub struct Job {
     #[serde(rename = "Region")]
     pub region: Option<String>,
     #[serde(rename = "Spreads")]
-    pub spreads: Option<::serde_json::Value>,
+    pub spreads: Option<Vec<Spread>>,
     #[serde(rename = "Stable")]
     pub stable: Option<bool>,
     #[serde(rename = "Status")]
-    pub status: RunningStatus,
+    pub status: JobStatus,
     #[serde(rename = "StatusDescription")]
     pub status_description: Option<String>,
     #[serde(rename = "Stop")]
@@ -446,10 +528,165 @@ pub struct Job {
     pub version: Option<i64>,
 }
 
+/// The `parameterized` block of a job, describing what its dispatches must provide.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterizedJobConfig {
+    #[serde(rename = "Payload")]
+    #[serde(default)]
+    pub payload: PayloadPolicy,
+    #[serde(rename = "MetaRequired")]
+    #[serde(default)]
+    pub meta_required: Option<Vec<String>>,
+    #[serde(rename = "MetaOptional")]
+    #[serde(default)]
+    pub meta_optional: Option<Vec<String>>,
+}
+
+impl ParameterizedJobConfig {
+    /// Lists what Nomad would reject in a dispatch with `payload` and `meta`, an empty list if
+    /// the dispatch is valid. An empty payload counts as no payload, like in Nomad.
+    pub fn check(
+        &self,
+        payload: Option<&[u8]>,
+        meta: &HashMap<String, String>,
+    ) -> Vec<DispatchViolation> {
+        let mut violations = Vec::new();
+        let has_payload = payload.is_some_and(|payload| !payload.is_empty());
+        match self.payload {
+            PayloadPolicy::Required if !has_payload => {
+                violations.push(DispatchViolation::PayloadRequired)
+            }
+            PayloadPolicy::Forbidden if has_payload => {
+                violations.push(DispatchViolation::PayloadForbidden)
+            }
+            _ => {}
+        }
+        let required = self.meta_required.as_deref().unwrap_or_default();
+        let optional = self.meta_optional.as_deref().unwrap_or_default();
+        for key in required {
+            if !meta.contains_key(key) {
+                violations.push(DispatchViolation::MissingMeta(key.clone()));
+            }
+        }
+        let mut unknown: Vec<&String> = meta
+            .keys()
+            .filter(|key| !required.contains(key) && !optional.contains(key))
+            .collect();
+        unknown.sort();
+        violations.extend(
+            unknown
+                .into_iter()
+                .map(|key| DispatchViolation::UnknownMeta(key.clone())),
+        );
+        violations
+    }
+}
+
+/// Whether dispatches of a parameterized job take a payload.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadPolicy {
+    #[default]
+    #[serde(alias = "")]
+    Optional,
+    Required,
+    Forbidden,
+}
+
+/// A reason for Nomad to reject a dispatch, see [`ParameterizedJobConfig::check`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DispatchViolation {
+    /// The job has no `parameterized` block and cannot be dispatched.
+    NotParameterized,
+    PayloadRequired,
+    PayloadForbidden,
+    /// A key of `MetaRequired` is missing.
+    MissingMeta(String),
+    /// A key is neither in `MetaRequired` nor in `MetaOptional`.
+    UnknownMeta(String),
+}
+
+impl fmt::Display for DispatchViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DispatchViolation::NotParameterized => write!(f, "job is not parameterized"),
+            DispatchViolation::PayloadRequired => write!(f, "payload is required"),
+            DispatchViolation::PayloadForbidden => write!(f, "payload is forbidden"),
+            DispatchViolation::MissingMeta(key) => write!(f, "missing required meta key `{}`", key),
+            DispatchViolation::UnknownMeta(key) => write!(f, "unknown meta key `{}`", key),
+        }
+    }
+}
+
+/// Options of [`crate::client::NomadClient::register_job`] and
+/// [`crate::client::NomadClient::update_job`].
+///
+/// ```
+/// use nomad_api::jobs::JobRegisterOptions;
+///
+/// // Only update the job if nobody changed it since it was read at modify index 1042.
+/// let options = JobRegisterOptions::check_index(1042);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JobRegisterOptions {
+    /// Registers the job only if its current `JobModifyIndex` is `job_modify_index`, or if it
+    /// does not exist yet when `job_modify_index` is 0.
+    pub enforce_index: bool,
+    pub job_modify_index: u64,
+    /// Keeps the current counts of the existing task groups instead of those of the job spec.
+    pub preserve_counts: bool,
+    /// Overrides soft mandatory Sentinel policies.
+    pub policy_override: bool,
+    /// Priority of the evaluation created by the registration, the job priority when unset.
+    pub eval_priority: Option<i64>,
+}
+
+impl JobRegisterOptions {
+    /// Check-and-set registration against `job_modify_index`.
+    pub fn check_index(job_modify_index: u64) -> Self {
+        JobRegisterOptions {
+            enforce_index: true,
+            job_modify_index,
+            ..JobRegisterOptions::default()
+        }
+    }
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#create-job
+/// Method	Path	Produces
+/// POST	/v1/jobs	application/json
+/// POST	/v1/job/:job_id	application/json
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CreateJobRequest {
     pub job: Job,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub enforce_index: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub preserve_counts: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub policy_override: bool,
+    #[serde(default)]
+    pub job_modify_index: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eval_priority: Option<i64>,
+}
+
+impl CreateJobRequest {
+    pub fn new(job: Job, options: &JobRegisterOptions) -> Self {
+        CreateJobRequest {
+            job,
+            enforce_index: options.enforce_index,
+            preserve_counts: options.preserve_counts,
+            policy_override: options.policy_override,
+            job_modify_index: options.job_modify_index,
+            eval_priority: options.eval_priority,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -475,9 +712,9 @@ pub struct CreateJobResponse {
 #[serde(rename_all = "camelCase")]
 pub struct TaskGroup {
     #[serde(rename = "Affinities")]
-    pub affinities: Option<::serde_json::Value>,
+    pub affinities: Option<Vec<Affinity>>,
     #[serde(rename = "Constraints")]
-    pub constraints: Option<::serde_json::Value>,
+    pub constraints: Option<Vec<Constraint>>,
     #[serde(rename = "Consul")]
     pub consul: Option<Consul>,
     #[serde(rename = "Count")]
@@ -500,11 +737,11 @@ pub struct TaskGroup {
     #[serde(rename = "Scaling")]
     pub scaling: Option<::serde_json::Value>,
     #[serde(rename = "Services")]
-    pub services: Option<::serde_json::Value>,
+    pub services: Option<Vec<Service>>,
     #[serde(rename = "ShutdownDelay")]
     pub shutdown_delay: Option<::serde_json::Value>,
     #[serde(rename = "Spreads")]
-    pub spreads: Option<::serde_json::Value>,
+    pub spreads: Option<Vec<Spread>>,
     #[serde(rename = "StopAfterClientDisconnect")]
     pub stop_after_client_disconnect: Option<::serde_json::Value>,
     #[serde(rename = "Tasks")]
@@ -581,7 +818,7 @@ pub struct RestartPolicy {
 #[serde(rename_all = "camelCase")]
 pub struct Task {
     #[serde(rename = "Affinities")]
-    pub affinities: Option<::serde_json::Value>,
+    pub affinities: Option<Vec<Affinity>>,
     #[serde(rename = "Artifacts")]
     pub artifacts: Option<::serde_json::Value>,
     #[serde(rename = "CSIPluginConfig")]
@@ -589,7 +826,7 @@ pub struct Task {
     #[serde(rename = "Config")]
     pub config: Config,
     #[serde(rename = "Constraints")]
-    pub constraints: Option<::serde_json::Value>,
+    pub constraints: Option<Vec<Constraint>>,
     #[serde(rename = "DispatchPayload")]
     pub dispatch_payload: Option<::serde_json::Value>,
     #[serde(rename = "Driver")]
@@ -619,7 +856,7 @@ pub struct Task {
     #[serde(rename = "ScalingPolicies")]
     pub scaling_policies: Option<::serde_json::Value>,
     #[serde(rename = "Services")]
-    pub services: Option<::serde_json::Value>,
+    pub services: Option<Vec<Service>>,
     #[serde(rename = "ShutdownDelay")]
     pub shutdown_delay: i64,
     #[serde(rename = "Templates")]
@@ -655,9 +892,9 @@ pub struct LogConfig {
 #[serde(rename_all = "camelCase")]
 pub struct Device {
     #[serde(rename = "Affinities")]
-    pub affinities: Option<::serde_json::Value>,
+    pub affinities: Option<Vec<Affinity>>,
     #[serde(rename = "Constraints")]
-    pub constraints: Option<::serde_json::Value>,
+    pub constraints: Option<Vec<Constraint>>,
     #[serde(rename = "Count")]
     pub count: i64,
     #[serde(rename = "Name")]
@@ -713,10 +950,12 @@ pub struct Metrics {
     pub coalesced_failures: i64,
     #[serde(rename = "ConstraintFiltered")]
     pub constraint_filtered: Option<::serde_json::Value>,
+    /// Nodes exhausted per resource dimension, e.g. `memory` or `devices: nvidia/gpu`.
     #[serde(rename = "DimensionExhausted")]
-    pub dimension_exhausted: Option<DimensionExhausted>,
+    pub dimension_exhausted: Option<HashMap<String, i64>>,
+    /// Nodes available per datacenter.
     #[serde(rename = "NodesAvailable")]
-    pub nodes_available: Option<NodesAvailable>,
+    pub nodes_available: Option<HashMap<String, i64>>,
     #[serde(rename = "NodesEvaluated")]
     pub nodes_evaluated: i64,
     #[serde(rename = "NodesExhausted")]
@@ -734,12 +973,14 @@ pub struct Metrics {
     pub scores: Option<::serde_json::Value>,
 }
 
+#[deprecated(note = "`Metrics::dimension_exhausted` is keyed by dimension")]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DimensionExhausted {
     pub disk: Option<i64>,
 }
 
+#[deprecated(note = "`Metrics::nodes_available` is keyed by datacenter")]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NodesAvailable {
@@ -839,7 +1080,7 @@ pub struct TaskState {
     #[serde(rename = "StartedAt")]
     pub started_at: Option<String>,
     #[serde(rename = "State")]
-    pub state: RunningStatus,
+    pub state: TaskStateKind,
     #[serde(rename = "TaskHandle")]
     pub task_handle: Option<::serde_json::Value>,
 }
@@ -922,3 +1163,684 @@ pub struct ParseJobPayload {
     pub job_hcl: String,
     pub canonicalize: bool,
 }
+
+/// https://www.nomadproject.io/api-docs/jobs#list-job-versions
+/// Method	Path	Produces
+/// GET	/v1/job/:job_id/versions	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobVersionsResponse {
+    /// Newest version first.
+    #[serde(rename = "Versions")]
+    pub versions: Vec<Job>,
+    /// Only set when requested with `diffs`: `diffs[i]` goes from `versions[i + 1]` to
+    /// `versions[i]`.
+    #[serde(rename = "Diffs")]
+    #[serde(default)]
+    pub diffs: Option<Vec<JobDiff>>,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#revert-to-older-job-version
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/revert	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRevertRequest {
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "JobVersion")]
+    pub job_version: u64,
+    #[serde(rename = "EnforcePriorVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_prior_version: Option<u64>,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#set-job-stability
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/stable	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobStabilityRequest {
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "JobVersion")]
+    pub job_version: u64,
+    #[serde(rename = "Stable")]
+    pub stable: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobStabilityResponse {
+    #[serde(rename = "Index")]
+    pub index: u64,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#read-job-summary
+/// Method	Path	Produces
+/// GET	/v1/job/:job_id/summary	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobSummary {
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "Namespace")]
+    #[serde(default)]
+    pub namespace: String,
+    /// Allocation counts keyed by task group.
+    #[serde(rename = "Summary")]
+    #[serde(default)]
+    pub summary: HashMap<String, TaskGroupSummary>,
+    /// Counts of the dispatched or launched children of a parameterized or periodic job.
+    #[serde(rename = "Children")]
+    #[serde(default)]
+    pub children: Option<JobChildrenSummary>,
+    #[serde(rename = "CreateIndex")]
+    pub create_index: u64,
+    #[serde(rename = "ModifyIndex")]
+    pub modify_index: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskGroupSummary {
+    #[serde(rename = "Queued")]
+    pub queued: u64,
+    #[serde(rename = "Complete")]
+    pub complete: u64,
+    #[serde(rename = "Failed")]
+    pub failed: u64,
+    #[serde(rename = "Running")]
+    pub running: u64,
+    #[serde(rename = "Starting")]
+    pub starting: u64,
+    #[serde(rename = "Lost")]
+    pub lost: u64,
+    #[serde(rename = "Unknown")]
+    #[serde(default)]
+    pub unknown: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobChildrenSummary {
+    #[serde(rename = "Pending")]
+    pub pending: u64,
+    #[serde(rename = "Running")]
+    pub running: u64,
+    #[serde(rename = "Dead")]
+    pub dead: u64,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#create-job-evaluation
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/evaluate	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobEvaluateRequest {
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "EvalOptions")]
+    pub eval_options: EvalOptions,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalOptions {
+    /// Reschedules failed allocations even if their reschedule policy is exhausted.
+    #[serde(rename = "ForceReschedule")]
+    pub force_reschedule: bool,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#force-new-periodic-instance
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/periodic/force	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodicForceResponse {
+    #[serde(rename = "EvalID")]
+    #[serde(default)]
+    pub eval_id: String,
+    #[serde(rename = "EvalCreateIndex")]
+    #[serde(default)]
+    pub eval_create_index: u64,
+    #[serde(rename = "Index")]
+    #[serde(default)]
+    pub index: u64,
+}
+
+/// https://www.nomadproject.io/api-docs/validate#validate-job
+/// Method	Path	Produces
+/// POST	/v1/validate/job	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobValidateRequest {
+    #[serde(rename = "Job")]
+    pub job: Job,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobValidateResponse {
+    /// Whether the task driver configurations were validated as well.
+    #[serde(rename = "DriverConfigValidated")]
+    #[serde(default)]
+    pub driver_config_validated: bool,
+    /// One entry per problem, e.g. `Task group worker validation failed: ...`.
+    #[serde(rename = "ValidationErrors")]
+    #[serde(default)]
+    pub validation_errors: Option<Vec<String>>,
+    /// All validation errors joined in a single message.
+    #[serde(rename = "Error")]
+    #[serde(default)]
+    pub error: String,
+    #[serde(rename = "Warnings")]
+    #[serde(default)]
+    pub warnings: String,
+}
+
+impl JobValidateResponse {
+    pub fn is_valid(&self) -> bool {
+        self.error.is_empty() && self.validation_errors.iter().flatten().next().is_none()
+    }
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#scale-task-group
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/scale	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScalingRequest {
+    /// New count of the group, `None` only records a scaling event.
+    #[serde(rename = "Count")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    #[serde(rename = "Target")]
+    pub target: HashMap<String, String>,
+    #[serde(rename = "Message")]
+    pub message: String,
+    #[serde(rename = "Error")]
+    pub error: bool,
+    #[serde(rename = "Meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, serde_json::Value>>,
+    #[serde(rename = "PolicyOverride")]
+    pub policy_override: bool,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#read-job-scale-status
+/// Method	Path	Produces
+/// GET	/v1/job/:job_id/scale	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobScaleStatus {
+    #[serde(rename = "JobID")]
+    pub job_id: String,
+    #[serde(rename = "Namespace")]
+    #[serde(default)]
+    pub namespace: String,
+    #[serde(rename = "JobCreateIndex")]
+    pub job_create_index: u64,
+    #[serde(rename = "JobModifyIndex")]
+    pub job_modify_index: u64,
+    #[serde(rename = "JobStopped")]
+    pub job_stopped: bool,
+    /// Keyed by task group.
+    #[serde(rename = "TaskGroups")]
+    #[serde(default)]
+    pub task_groups: HashMap<String, TaskGroupScaleStatus>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskGroupScaleStatus {
+    #[serde(rename = "Desired")]
+    pub desired: i64,
+    #[serde(rename = "Placed")]
+    pub placed: i64,
+    #[serde(rename = "Running")]
+    pub running: i64,
+    #[serde(rename = "Healthy")]
+    pub healthy: i64,
+    #[serde(rename = "Unhealthy")]
+    pub unhealthy: i64,
+    /// Most recent first.
+    #[serde(rename = "Events")]
+    #[serde(default)]
+    pub events: Option<Vec<ScalingEvent>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScalingEvent {
+    #[serde(rename = "Count")]
+    #[serde(default)]
+    pub count: Option<i64>,
+    #[serde(rename = "PreviousCount")]
+    #[serde(default)]
+    pub previous_count: i64,
+    #[serde(rename = "Error")]
+    #[serde(default)]
+    pub error: bool,
+    #[serde(rename = "Message")]
+    #[serde(default)]
+    pub message: String,
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: Option<HashMap<String, serde_json::Value>>,
+    #[serde(rename = "EvalID")]
+    #[serde(default)]
+    pub eval_id: Option<String>,
+    /// Unix time in nanoseconds.
+    #[serde(rename = "Time")]
+    #[serde(default)]
+    pub time: u64,
+    #[serde(rename = "CreateIndex")]
+    #[serde(default)]
+    pub create_index: u64,
+}
+
+/// https://www.nomadproject.io/api-docs/jobs#create-job-plan
+/// Method	Path	Produces
+/// POST	/v1/job/:job_id/plan	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobPlanRequest {
+    #[serde(rename = "Job")]
+    pub job: Job,
+    #[serde(rename = "Diff")]
+    pub diff: bool,
+    #[serde(rename = "PolicyOverride")]
+    pub policy_override: bool,
+}
+
+/// Outcome of a scheduler dry-run.
+///
+/// The [`fmt::Display`] implementation renders it like `nomad job plan`:
+///
+/// ```text
+/// +/- Job: "numpire-worker-gpu"
+/// +/- Priority: "50" => "70"
+///     Task Group: "worker"
+///   +/- Task: "worker" (forces create/destroy update)
+///     +/- Config {
+///       +/- image: "worker-gpu:1.4.2" => "worker-gpu:1.5.0"
+///         }
+///
+/// Scheduler dry-run:
+/// - All tasks successfully allocated.
+///
+/// Job Modify Index: 1042
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobPlanResponse {
+    /// Pass it to [`JobRegisterOptions::check_index`] to register the planned job only if it
+    /// did not change since.
+    #[serde(rename = "JobModifyIndex")]
+    pub job_modify_index: u64,
+    #[serde(rename = "CreatedEvals")]
+    #[serde(default)]
+    pub created_evals: Option<Vec<Evaluation>>,
+    /// Only set when the plan was requested with `diff`.
+    #[serde(rename = "Diff")]
+    #[serde(default)]
+    pub diff: Option<JobDiff>,
+    #[serde(rename = "Annotations")]
+    #[serde(default)]
+    pub annotations: Option<PlanAnnotations>,
+    /// Placement failures keyed by task group.
+    #[serde(rename = "FailedTGAllocs")]
+    #[serde(default)]
+    pub failed_tg_allocs: Option<HashMap<String, Metrics>>,
+    /// RFC 3339 time of the next launch of a periodic job, the zero time otherwise.
+    #[serde(rename = "NextPeriodicLaunch")]
+    #[serde(default)]
+    pub next_periodic_launch: Option<String>,
+    #[serde(rename = "Warnings")]
+    #[serde(default)]
+    pub warnings: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanAnnotations {
+    /// Scheduler decisions keyed by task group.
+    #[serde(rename = "DesiredTGUpdates")]
+    #[serde(default)]
+    pub desired_tg_updates: Option<HashMap<String, DesiredUpdates>>,
+    #[serde(rename = "PreemptedAllocs")]
+    #[serde(default)]
+    pub preempted_allocs: Option<Vec<AllocationListStub>>,
+}
+
+/// Number of allocations of a task group per scheduler decision.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesiredUpdates {
+    #[serde(rename = "Ignore")]
+    pub ignore: u64,
+    #[serde(rename = "Place")]
+    pub place: u64,
+    #[serde(rename = "Migrate")]
+    pub migrate: u64,
+    #[serde(rename = "Stop")]
+    pub stop: u64,
+    #[serde(rename = "InPlaceUpdate")]
+    pub in_place_update: u64,
+    #[serde(rename = "DestructiveUpdate")]
+    pub destructive_update: u64,
+    #[serde(rename = "Canary")]
+    pub canary: u64,
+    #[serde(rename = "Preemptions")]
+    #[serde(default)]
+    pub preemptions: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DiffType {
+    Added,
+    Deleted,
+    Edited,
+    #[default]
+    None,
+}
+
+impl DiffType {
+    /// Marker printed in front of a diff line.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            DiffType::Added => "+",
+            DiffType::Deleted => "-",
+            DiffType::Edited => "+/-",
+            DiffType::None => "",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobDiff {
+    #[serde(rename = "Type")]
+    pub type_field: DiffType,
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Fields")]
+    #[serde(default)]
+    pub fields: Option<Vec<FieldDiff>>,
+    #[serde(rename = "Objects")]
+    #[serde(default)]
+    pub objects: Option<Vec<ObjectDiff>>,
+    #[serde(rename = "TaskGroups")]
+    #[serde(default)]
+    pub task_groups: Option<Vec<TaskGroupDiff>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskGroupDiff {
+    #[serde(rename = "Type")]
+    pub type_field: DiffType,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Fields")]
+    #[serde(default)]
+    pub fields: Option<Vec<FieldDiff>>,
+    #[serde(rename = "Objects")]
+    #[serde(default)]
+    pub objects: Option<Vec<ObjectDiff>>,
+    #[serde(rename = "Tasks")]
+    #[serde(default)]
+    pub tasks: Option<Vec<TaskDiff>>,
+    /// Number of allocations per kind of update, e.g. `create/destroy update`.
+    #[serde(rename = "Updates")]
+    #[serde(default)]
+    pub updates: Option<HashMap<String, u64>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskDiff {
+    #[serde(rename = "Type")]
+    pub type_field: DiffType,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Fields")]
+    #[serde(default)]
+    pub fields: Option<Vec<FieldDiff>>,
+    #[serde(rename = "Objects")]
+    #[serde(default)]
+    pub objects: Option<Vec<ObjectDiff>>,
+    /// e.g. `forces create/destroy update`.
+    #[serde(rename = "Annotations")]
+    #[serde(default)]
+    pub annotations: Option<Vec<String>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    #[serde(rename = "Type")]
+    pub type_field: DiffType,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Old")]
+    #[serde(default)]
+    pub old: String,
+    #[serde(rename = "New")]
+    #[serde(default)]
+    pub new: String,
+    #[serde(rename = "Annotations")]
+    #[serde(default)]
+    pub annotations: Option<Vec<String>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectDiff {
+    #[serde(rename = "Type")]
+    pub type_field: DiffType,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Fields")]
+    #[serde(default)]
+    pub fields: Option<Vec<FieldDiff>>,
+    #[serde(rename = "Objects")]
+    #[serde(default)]
+    pub objects: Option<Vec<ObjectDiff>>,
+}
+
+impl fmt::Display for JobPlanResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(diff) = &self.diff {
+            write!(f, "{}", diff)?;
+            writeln!(f)?;
+        }
+        writeln!(f, "Scheduler dry-run:")?;
+        match &self.failed_tg_allocs {
+            Some(failed) if !failed.is_empty() => {
+                writeln!(f, "- WARNING: Failed to place all allocations.")?;
+                let mut groups: Vec<_> = failed.iter().collect();
+                groups.sort_by_key(|(name, _)| name.as_str());
+                for (name, metrics) in groups {
+                    write_placement_failure(f, name, metrics)?;
+                }
+            }
+            _ => writeln!(f, "- All tasks successfully allocated.")?,
+        }
+        if let Some(launch) = &self.next_periodic_launch {
+            if !launch.starts_with("0001-01-01") {
+                writeln!(
+                    f,
+                    "- If submitted now, next periodic launch would be at {}.",
+                    launch
+                )?;
+            }
+        }
+        if !self.warnings.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Job Warnings:")?;
+            writeln!(f, "{}", self.warnings.trim_end())?;
+        }
+        writeln!(f)?;
+        write!(f, "Job Modify Index: {}", self.job_modify_index)
+    }
+}
+
+fn write_placement_failure(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    metrics: &Metrics,
+) -> fmt::Result {
+    let count = metrics.coalesced_failures + 1;
+    writeln!(
+        f,
+        "  Task Group \"{}\" (failed to place {} allocation{}):",
+        name,
+        count,
+        if count == 1 { "" } else { "s" }
+    )?;
+    if metrics.nodes_evaluated == 0 {
+        writeln!(f, "    * No nodes were eligible for evaluation")?;
+    }
+    if let Some(serde_json::Value::Object(filtered)) = &metrics.constraint_filtered {
+        for (constraint, nodes) in filtered {
+            writeln!(
+                f,
+                "    * Constraint \"{}\": {} nodes excluded by filter",
+                constraint, nodes
+            )?;
+        }
+    }
+    if metrics.nodes_exhausted > 0 {
+        writeln!(
+            f,
+            "    * Resources exhausted on {} nodes",
+            metrics.nodes_exhausted
+        )?;
+    }
+    let mut exhausted: Vec<_> = metrics.dimension_exhausted.iter().flatten().collect();
+    exhausted.sort();
+    for (dimension, nodes) in exhausted {
+        writeln!(
+            f,
+            "    * Dimension \"{}\" exhausted on {} nodes",
+            dimension, nodes
+        )?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for JobDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_header(
+            f,
+            0,
+            self.type_field,
+            &format!("Job: \"{}\"", self.id),
+            None,
+        )?;
+        write_fields(f, 0, &self.fields)?;
+        write_objects(f, 0, &self.objects)?;
+        for group in self.task_groups.iter().flatten() {
+            write!(f, "{}", group)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TaskGroupDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut updates: Vec<_> = self
+            .updates
+            .iter()
+            .flatten()
+            .filter(|(_, count)| **count > 0)
+            .collect();
+        updates.sort();
+        let updates = updates
+            .iter()
+            .map(|(update, count)| format!("{} {}", count, update))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let title = format!("Task Group: \"{}\"", self.name);
+        write_header(
+            f,
+            0,
+            self.type_field,
+            &title,
+            Some(updates.as_str()).filter(|u| !u.is_empty()),
+        )?;
+        write_fields(f, 2, &self.fields)?;
+        write_objects(f, 2, &self.objects)?;
+        for task in self.tasks.iter().flatten() {
+            if task.type_field == DiffType::None {
+                continue;
+            }
+            let title = format!("Task: \"{}\"", task.name);
+            write_header(
+                f,
+                2,
+                task.type_field,
+                &title,
+                annotations(&task.annotations).as_deref(),
+            )?;
+            write_fields(f, 4, &task.fields)?;
+            write_objects(f, 4, &task.objects)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `title` after the marker of `kind`, right-aligning markers like the Nomad CLI.
+fn write_header(
+    f: &mut fmt::Formatter<'_>,
+    indent: usize,
+    kind: DiffType,
+    title: &str,
+    annotation: Option<&str>,
+) -> fmt::Result {
+    write!(
+        f,
+        "{:indent$}{:>3} {}",
+        "",
+        kind.marker(),
+        title,
+        indent = indent
+    )?;
+    match annotation {
+        Some(annotation) => writeln!(f, " ({})", annotation),
+        None => writeln!(f),
+    }
+}
+
+fn write_fields(
+    f: &mut fmt::Formatter<'_>,
+    indent: usize,
+    fields: &Option<Vec<FieldDiff>>,
+) -> fmt::Result {
+    for field in fields.iter().flatten() {
+        let line = match field.type_field {
+            DiffType::Added => format!("{}: \"{}\"", field.name, field.new),
+            DiffType::Deleted => format!("{}: \"{}\"", field.name, field.old),
+            DiffType::Edited => format!("{}: \"{}\" => \"{}\"", field.name, field.old, field.new),
+            DiffType::None => continue,
+        };
+        write_header(
+            f,
+            indent,
+            field.type_field,
+            &line,
+            annotations(&field.annotations).as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_objects(
+    f: &mut fmt::Formatter<'_>,
+    indent: usize,
+    objects: &Option<Vec<ObjectDiff>>,
+) -> fmt::Result {
+    for object in objects.iter().flatten() {
+        if object.type_field == DiffType::None {
+            continue;
+        }
+        write_header(
+            f,
+            indent,
+            object.type_field,
+            &format!("{} {{", object.name),
+            None,
+        )?;
+        write_fields(f, indent + 2, &object.fields)?;
+        write_objects(f, indent + 2, &object.objects)?;
+        writeln!(f, "{:indent$}    }}", "", indent = indent)?;
+    }
+    Ok(())
+}
+
+/// Joins annotations such as `forces create/destroy update`.
+fn annotations(annotations: &Option<Vec<String>>) -> Option<String> {
+    let annotations: Vec<&str> = annotations.iter().flatten().map(String::as_str).collect();
+    if annotations.is_empty() {
+        None
+    } else {
+        Some(annotations.join(", "))
+    }
+}
@@ -1,5 +1,17 @@
 #![allow(clippy::tabs_in_doc_comments)]
 pub mod client;
 pub mod client_model;
+pub mod constraints;
+pub mod dispatch;
+pub mod error;
+pub mod evaluations;
+pub mod events;
+pub mod exec;
+pub mod fs;
 pub mod jobs;
 pub mod nodes;
+pub mod query;
+pub mod services;
+pub mod status;
+pub mod tls;
+pub mod watch;
@@ -1 +1,387 @@
+use crate::jobs::Network;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// https://www.nomadproject.io/api-docs/nodes#list-nodes
+/// Method	Path	Produces
+/// GET	/v1/nodes	application/json
+///
+/// `NodeResources` and `ReservedResources` are only set when the list is requested with
+/// `resources=true`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeListStub {
+    #[serde(rename = "Address")]
+    pub address: String,
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Attributes")]
+    #[serde(default)]
+    pub attributes: Option<HashMap<String, String>>,
+    #[serde(rename = "Datacenter")]
+    pub datacenter: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "NodeClass")]
+    pub node_class: String,
+    #[serde(rename = "NodePool")]
+    #[serde(default)]
+    pub node_pool: Option<String>,
+    #[serde(rename = "Version")]
+    pub version: String,
+    #[serde(rename = "Drain")]
+    pub drain: bool,
+    #[serde(rename = "SchedulingEligibility")]
+    pub scheduling_eligibility: String,
+    #[serde(rename = "Status")]
+    pub status: String,
+    #[serde(rename = "StatusDescription")]
+    pub status_description: String,
+    #[serde(rename = "Drivers")]
+    #[serde(default)]
+    pub drivers: HashMap<String, DriverInfo>,
+    #[serde(rename = "NodeResources")]
+    #[serde(default)]
+    pub node_resources: Option<NodeResources>,
+    #[serde(rename = "ReservedResources")]
+    #[serde(default)]
+    pub reserved_resources: Option<NodeReservedResources>,
+    #[serde(rename = "LastDrain")]
+    #[serde(default)]
+    pub last_drain: Option<DrainMetadata>,
+    #[serde(rename = "CreateIndex")]
+    pub create_index: i64,
+    #[serde(rename = "ModifyIndex")]
+    pub modify_index: i64,
+}
+
+/// https://www.nomadproject.io/api-docs/nodes#read-node
+/// Method	Path	Produces
+/// GET	/v1/node/:node_id	application/json
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Datacenter")]
+    pub datacenter: String,
+    #[serde(rename = "NodeClass")]
+    pub node_class: String,
+    #[serde(rename = "NodePool")]
+    #[serde(default)]
+    pub node_pool: Option<String>,
+    #[serde(rename = "ComputedClass")]
+    #[serde(default)]
+    pub computed_class: String,
+    #[serde(rename = "HTTPAddr")]
+    pub http_addr: String,
+    #[serde(rename = "TLSEnabled")]
+    pub tls_enabled: bool,
+    #[serde(rename = "Attributes")]
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: Option<HashMap<String, String>>,
+    #[serde(rename = "Links")]
+    #[serde(default)]
+    pub links: Option<HashMap<String, String>>,
+    #[serde(rename = "Drivers")]
+    #[serde(default)]
+    pub drivers: HashMap<String, DriverInfo>,
+    #[serde(rename = "NodeResources")]
+    pub node_resources: Option<NodeResources>,
+    #[serde(rename = "ReservedResources")]
+    pub reserved_resources: Option<NodeReservedResources>,
+    #[serde(rename = "HostVolumes")]
+    #[serde(default)]
+    pub host_volumes: Option<HashMap<String, ::serde_json::Value>>,
+    #[serde(rename = "HostNetworks")]
+    #[serde(default)]
+    pub host_networks: Option<HashMap<String, ::serde_json::Value>>,
+    #[serde(rename = "CgroupParent")]
+    #[serde(default)]
+    pub cgroup_parent: Option<String>,
+    #[serde(rename = "Drain")]
+    pub drain: bool,
+    #[serde(rename = "DrainStrategy")]
+    pub drain_strategy: Option<DrainStrategy>,
+    #[serde(rename = "LastDrain")]
+    #[serde(default)]
+    pub last_drain: Option<DrainMetadata>,
+    #[serde(rename = "SchedulingEligibility")]
+    pub scheduling_eligibility: String,
+    #[serde(rename = "Status")]
+    pub status: String,
+    #[serde(rename = "StatusDescription")]
+    pub status_description: String,
+    #[serde(rename = "StatusUpdatedAt")]
+    pub status_updated_at: i64,
+    #[serde(rename = "Events")]
+    #[serde(default)]
+    pub events: Option<Vec<NodeEvent>>,
+    #[serde(rename = "CreateIndex")]
+    pub create_index: i64,
+    #[serde(rename = "ModifyIndex")]
+    pub modify_index: i64,
+}
+
+impl Node {
+    /// Device groups of type `gpu` fingerprinted on this node.
+    pub fn gpus(&self) -> Vec<&NodeDeviceResource> {
+        self.node_resources
+            .iter()
+            .flat_map(|r| r.devices.iter().flatten())
+            .filter(|d| d.type_field == "gpu")
+            .collect()
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriverInfo {
+    #[serde(rename = "Attributes")]
+    #[serde(default)]
+    pub attributes: Option<HashMap<String, String>>,
+    #[serde(rename = "Detected")]
+    pub detected: bool,
+    #[serde(rename = "Healthy")]
+    pub healthy: bool,
+    #[serde(rename = "HealthDescription")]
+    pub health_description: String,
+    #[serde(rename = "UpdateTime")]
+    pub update_time: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeResources {
+    #[serde(rename = "Cpu")]
+    pub cpu: NodeCpuResources,
+    #[serde(rename = "Memory")]
+    pub memory: NodeMemoryResources,
+    #[serde(rename = "Disk")]
+    pub disk: NodeDiskResources,
+    #[serde(rename = "Networks")]
+    #[serde(default)]
+    pub networks: Option<Vec<Network>>,
+    #[serde(rename = "Devices")]
+    #[serde(default)]
+    pub devices: Option<Vec<NodeDeviceResource>>,
+    #[serde(rename = "MinDynamicPort")]
+    #[serde(default)]
+    pub min_dynamic_port: i64,
+    #[serde(rename = "MaxDynamicPort")]
+    #[serde(default)]
+    pub max_dynamic_port: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeCpuResources {
+    #[serde(rename = "CpuShares")]
+    pub cpu_shares: i64,
+    #[serde(rename = "TotalCpuCores")]
+    #[serde(default)]
+    pub total_cpu_cores: i64,
+    #[serde(rename = "ReservableCpuCores")]
+    #[serde(default)]
+    pub reservable_cpu_cores: Option<Vec<u16>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMemoryResources {
+    #[serde(rename = "MemoryMB")]
+    pub memory_mb: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDiskResources {
+    #[serde(rename = "DiskMB")]
+    pub disk_mb: i64,
+}
+
+/// A group of identical devices, e.g. all `nvidia/gpu/Tesla T4` of a node.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDeviceResource {
+    #[serde(rename = "Vendor")]
+    pub vendor: String,
+    #[serde(rename = "Type")]
+    pub type_field: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Instances")]
+    #[serde(default)]
+    pub instances: Option<Vec<NodeDevice>>,
+    #[serde(rename = "Attributes")]
+    #[serde(default)]
+    pub attributes: Option<HashMap<String, DeviceAttribute>>,
+}
+
+impl NodeDeviceResource {
+    /// Number of instances currently reported healthy.
+    pub fn healthy_count(&self) -> usize {
+        self.instances
+            .iter()
+            .flatten()
+            .filter(|instance| instance.healthy)
+            .count()
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDevice {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Healthy")]
+    pub healthy: bool,
+    #[serde(rename = "HealthDescription")]
+    pub health_description: String,
+    #[serde(rename = "Locality")]
+    pub locality: Option<NodeDeviceLocality>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDeviceLocality {
+    #[serde(rename = "PciBusID")]
+    pub pci_bus_id: String,
+}
+
+/// Typed device attribute, e.g. `memory` = `{"IntVal": 15109, "Unit": "MiB"}`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceAttribute {
+    #[serde(rename = "FloatVal")]
+    pub float_val: Option<f64>,
+    #[serde(rename = "IntVal")]
+    pub int_val: Option<i64>,
+    #[serde(rename = "StringVal")]
+    pub string_val: Option<String>,
+    #[serde(rename = "BoolVal")]
+    pub bool_val: Option<bool>,
+    #[serde(rename = "Unit")]
+    pub unit: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeReservedResources {
+    #[serde(rename = "Cpu")]
+    pub cpu: NodeReservedCpuResources,
+    #[serde(rename = "Memory")]
+    pub memory: NodeMemoryResources,
+    #[serde(rename = "Disk")]
+    pub disk: NodeDiskResources,
+    #[serde(rename = "Networks")]
+    pub networks: NodeReservedNetworkResources,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeReservedCpuResources {
+    #[serde(rename = "CpuShares")]
+    pub cpu_shares: i64,
+    #[serde(rename = "ReservedCpuCores")]
+    #[serde(default)]
+    pub reserved_cpu_cores: Option<Vec<u16>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeReservedNetworkResources {
+    #[serde(rename = "ReservedHostPorts")]
+    pub reserved_host_ports: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrainStrategy {
+    /// Nanoseconds, `-1` forces an immediate drain and `0` means no deadline.
+    #[serde(rename = "Deadline")]
+    pub deadline: i64,
+    #[serde(rename = "IgnoreSystemJobs")]
+    pub ignore_system_jobs: bool,
+    #[serde(rename = "ForceDeadline")]
+    pub force_deadline: Option<String>,
+    #[serde(rename = "StartedAt")]
+    pub started_at: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrainMetadata {
+    #[serde(rename = "StartedAt")]
+    pub started_at: String,
+    #[serde(rename = "UpdatedAt")]
+    pub updated_at: String,
+    #[serde(rename = "Status")]
+    pub status: String,
+    #[serde(rename = "AccessorID")]
+    #[serde(default)]
+    pub accessor_id: Option<String>,
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeEvent {
+    #[serde(rename = "Message")]
+    pub message: String,
+    #[serde(rename = "Subsystem")]
+    pub subsystem: String,
+    #[serde(rename = "Details")]
+    #[serde(default)]
+    pub details: Option<HashMap<String, String>>,
+    #[serde(rename = "Timestamp")]
+    pub timestamp: String,
+    #[serde(rename = "CreateIndex")]
+    pub create_index: i64,
+}
+
+/// https://www.nomadproject.io/api-docs/nodes#drain-node
+///
+/// DrainSpec (DrainSpec: nil) - Specifies if drain mode should be enabled. A missing or null
+/// value disables an existing drain.
+///
+/// MarkEligible (bool: false) - Specifies whether to mark a node as eligible for scheduling again
+/// when disabling a drain.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeUpdateDrainRequest {
+    #[serde(rename = "NodeID")]
+    pub node_id: String,
+    #[serde(rename = "DrainSpec")]
+    pub drain_spec: Option<DrainSpec>,
+    #[serde(rename = "MarkEligible")]
+    pub mark_eligible: bool,
+    #[serde(rename = "Meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrainSpec {
+    /// Nanoseconds after which remaining allocations are force-stopped, `-1` to stop them
+    /// immediately and `0` for no deadline.
+    #[serde(rename = "Deadline")]
+    pub deadline: i64,
+    #[serde(rename = "IgnoreSystemJobs")]
+    pub ignore_system_jobs: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeUpdateEligibilityRequest {
+    #[serde(rename = "NodeID")]
+    pub node_id: String,
+    /// `eligible` or `ineligible`.
+    #[serde(rename = "Eligibility")]
+    pub eligibility: String,
+}
+
+/// Answer of the drain, eligibility, purge and evaluate endpoints.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeUpdateResponse {
+    #[serde(rename = "EvalIDs")]
+    #[serde(default)]
+    pub eval_ids: Option<Vec<String>>,
+    #[serde(rename = "EvalCreateIndex")]
+    #[serde(default)]
+    pub eval_create_index: i64,
+    #[serde(rename = "NodeModifyIndex")]
+    #[serde(default)]
+    pub node_modify_index: i64,
+    #[serde(rename = "Index")]
+    #[serde(default)]
+    pub index: i64,
+}
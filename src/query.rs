@@ -0,0 +1,116 @@
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Options accepted by every read endpoint.
+///
+/// https://www.nomadproject.io/api-docs#blocking-queries
+///
+/// Setting `index` turns the call into a blocking query: Nomad holds the request until the
+/// index of the requested object moves past `index` or `wait` elapses, whichever comes first.
+/// Pass the [`QueryMeta::last_index`] of the previous answer to wait for the next change.
+///
+/// ```no_run
+/// # async fn watch(client: nomad_api::client::NomadClient) -> Result<(), nomad_api::error::NomadError> {
+/// use nomad_api::query::QueryOptions;
+/// use std::time::Duration;
+///
+/// let (_, meta) = client.list_jobs(&QueryOptions::default()).await?;
+/// let q = QueryOptions {
+///     index: Some(meta.last_index),
+///     wait: Some(Duration::from_secs(300)),
+///     ..QueryOptions::default()
+/// };
+/// let (jobs, _) = client.list_jobs(&q).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryOptions {
+    /// Index to block on, usually the `last_index` of a previous answer.
+    pub index: Option<u64>,
+    /// Maximum time Nomad holds a blocking query, capped at 10 minutes by the server.
+    pub wait: Option<Duration>,
+    /// Allows any server to answer instead of forwarding the query to the leader.
+    pub stale: bool,
+    /// Region to query, overriding the default region of the client.
+    pub region: Option<String>,
+    /// Namespace to query, overriding the default namespace of the client. `*` selects all
+    /// namespaces on the list endpoints that support it.
+    pub namespace: Option<String>,
+    /// Restricts list answers to IDs starting with this prefix.
+    pub prefix: Option<String>,
+    /// Filter expression applied by Nomad on list answers.
+    pub filter: Option<String>,
+    /// Maximum number of results per page.
+    pub per_page: Option<u32>,
+    /// Token of the page to read, taken from [`QueryMeta::next_token`].
+    pub next_token: Option<String>,
+    /// Additional endpoint specific query parameters.
+    pub params: HashMap<String, String>,
+}
+
+impl QueryOptions {
+    /// Query parameters, without region and namespace which fall back to the client defaults.
+    pub(crate) fn to_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(index) = self.index {
+            params.push(("index".to_string(), index.to_string()));
+        }
+        if let Some(wait) = self.wait {
+            params.push(("wait".to_string(), format!("{}ms", wait.as_millis())));
+        }
+        if self.stale {
+            params.push(("stale".to_string(), String::new()));
+        }
+        if let Some(prefix) = &self.prefix {
+            params.push(("prefix".to_string(), prefix.clone()));
+        }
+        if let Some(filter) = &self.filter {
+            params.push(("filter".to_string(), filter.clone()));
+        }
+        if let Some(per_page) = self.per_page {
+            params.push(("per_page".to_string(), per_page.to_string()));
+        }
+        if let Some(next_token) = &self.next_token {
+            params.push(("next_token".to_string(), next_token.clone()));
+        }
+        for (key, value) in &self.params {
+            params.push((key.clone(), value.clone()));
+        }
+        params
+    }
+}
+
+/// Metadata Nomad returns in the headers of every read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryMeta {
+    /// `X-Nomad-Index`: index to pass as [`QueryOptions::index`] to wait for the next change.
+    pub last_index: u64,
+    /// `X-Nomad-LastContact`: time since the answering server last heard from the leader.
+    pub last_contact: Duration,
+    /// `X-Nomad-KnownLeader`: whether the answering server knew about a leader.
+    pub known_leader: bool,
+    /// `X-Nomad-NextToken`: token of the next page, if there is one.
+    pub next_token: Option<String>,
+}
+
+impl QueryMeta {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        QueryMeta {
+            last_index: header("X-Nomad-Index")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default(),
+            last_contact: Duration::from_millis(
+                header("X-Nomad-LastContact")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or_default(),
+            ),
+            known_leader: header("X-Nomad-KnownLeader") == Some("true"),
+            next_token: header("X-Nomad-NextToken")
+                .filter(|v| !v.is_empty())
+                .map(str::to_string),
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Services registered by task groups and tasks, and their health checks.
+//!
+//! https://www.nomadproject.io/docs/job-specification/service
+//!
+//! Durations are in nanoseconds, like everywhere in the Nomad API.
+//!
+//! ```
+//! use nomad_api::services::{Service, ServiceCheck, ServiceProvider};
+//! use std::time::Duration;
+//!
+//! let service = Service {
+//!     provider: ServiceProvider::Nomad,
+//!     tags: Some(vec!["gpu".to_string()]),
+//!     checks: Some(vec![ServiceCheck::http(
+//!         "/health",
+//!         Duration::from_secs(10),
+//!         Duration::from_secs(2),
+//!     )]),
+//!     ..Service::new("numpire-worker", "http")
+//! };
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Enum of lowercase wire values keeping values this crate does not know in `Other`, like the
+/// statuses of [`crate::status`]. The empty string reads as the default.
+macro_rules! wire_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)+
+        }
+        default: $default:ident,
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// A value this crate does not know about.
+            Other(String),
+        }
+
+        impl $name {
+            /// The value as Nomad spells it.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::$default
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    "" => $name::$default,
+                    $($value => $name::$variant,)+
+                    value => $name::Other(value.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+/// https://www.nomadproject.io/docs/job-specification/service
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Service {
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// Label of a port of the group network, or a port number with the `driver` address mode.
+    #[serde(rename = "PortLabel")]
+    #[serde(default)]
+    pub port_label: String,
+    #[serde(rename = "Tags")]
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Tags replacing `tags` while the allocation is a canary.
+    #[serde(rename = "CanaryTags")]
+    #[serde(default)]
+    pub canary_tags: Option<Vec<String>>,
+    #[serde(rename = "EnableTagOverride")]
+    #[serde(default)]
+    pub enable_tag_override: bool,
+    #[serde(rename = "Provider")]
+    #[serde(default)]
+    pub provider: ServiceProvider,
+    #[serde(rename = "AddressMode")]
+    #[serde(default)]
+    pub address_mode: AddressMode,
+    /// Advertised address overriding the one picked by `address_mode`.
+    #[serde(rename = "Address")]
+    #[serde(default)]
+    pub address: String,
+    #[serde(rename = "Checks")]
+    #[serde(default)]
+    pub checks: Option<Vec<ServiceCheck>>,
+    /// Default of the checks that do not set their own.
+    #[serde(rename = "CheckRestart")]
+    #[serde(default)]
+    pub check_restart: Option<CheckRestart>,
+    /// Consul Connect sidecar configuration, Consul services only.
+    #[serde(rename = "Connect")]
+    #[serde(default)]
+    pub connect: Option<ConsulConnect>,
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: Option<HashMap<String, String>>,
+    #[serde(rename = "CanaryMeta")]
+    #[serde(default)]
+    pub canary_meta: Option<HashMap<String, String>>,
+    #[serde(rename = "TaggedAddresses")]
+    #[serde(default)]
+    pub tagged_addresses: Option<HashMap<String, String>>,
+    /// Task the service belongs to, required for group services using `script` checks.
+    #[serde(rename = "TaskName")]
+    #[serde(default)]
+    pub task_name: String,
+    /// `require_healthy`, `ignore_warnings` or `ignore`.
+    #[serde(rename = "OnUpdate")]
+    #[serde(default)]
+    pub on_update: String,
+}
+
+impl Service {
+    /// Service `name` on the port labelled `port_label`, everything else at Nomad's defaults.
+    pub fn new(name: impl Into<String>, port_label: impl Into<String>) -> Self {
+        Service {
+            name: name.into(),
+            port_label: port_label.into(),
+            ..Service::default()
+        }
+    }
+}
+
+wire_enum! {
+    /// Catalog a service is registered in.
+    ServiceProvider {
+        Consul => "consul",
+        Nomad => "nomad",
+    }
+    default: Consul,
+}
+
+wire_enum! {
+    /// Which address is advertised for a service or used by a check.
+    AddressMode {
+        /// The driver address if the driver advertises one, the host address otherwise.
+        Auto => "auto",
+        Host => "host",
+        Driver => "driver",
+        Alloc => "alloc",
+    }
+    default: Auto,
+}
+
+/// https://www.nomadproject.io/docs/job-specification/check
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceCheck {
+    #[serde(rename = "Name")]
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "Type")]
+    pub type_field: CheckType,
+    /// Nanoseconds between two checks.
+    #[serde(rename = "Interval")]
+    pub interval: i64,
+    /// Nanoseconds before a check fails.
+    #[serde(rename = "Timeout")]
+    pub timeout: i64,
+    /// Path of `http` checks.
+    #[serde(rename = "Path")]
+    #[serde(default)]
+    pub path: String,
+    /// `http` or `https` for `http` checks.
+    #[serde(rename = "Protocol")]
+    #[serde(default)]
+    pub protocol: String,
+    #[serde(rename = "Method")]
+    #[serde(default)]
+    pub method: String,
+    #[serde(rename = "Header")]
+    #[serde(default)]
+    pub header: Option<HashMap<String, Vec<String>>>,
+    #[serde(rename = "Body")]
+    #[serde(default)]
+    pub body: String,
+    /// Command of `script` checks, run inside the task.
+    #[serde(rename = "Command")]
+    #[serde(default)]
+    pub command: String,
+    #[serde(rename = "Args")]
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+    /// Port to check, the port of the service if empty.
+    #[serde(rename = "PortLabel")]
+    #[serde(default)]
+    pub port_label: String,
+    #[serde(rename = "AddressMode")]
+    #[serde(default)]
+    pub address_mode: AddressMode,
+    /// Exposes the check through the Connect sidecar proxy.
+    #[serde(rename = "Expose")]
+    #[serde(default)]
+    pub expose: bool,
+    #[serde(rename = "GRPCService")]
+    #[serde(default)]
+    pub grpc_service: String,
+    #[serde(rename = "GRPCUseTLS")]
+    #[serde(default)]
+    pub grpc_use_tls: bool,
+    #[serde(rename = "TLSSkipVerify")]
+    #[serde(default)]
+    pub tls_skip_verify: bool,
+    /// `passing`, `warning` or `critical`, Consul checks only.
+    #[serde(rename = "InitialStatus")]
+    #[serde(default)]
+    pub initial_status: String,
+    #[serde(rename = "SuccessBeforePassing")]
+    #[serde(default)]
+    pub success_before_passing: i64,
+    #[serde(rename = "FailuresBeforeCritical")]
+    #[serde(default)]
+    pub failures_before_critical: i64,
+    #[serde(rename = "CheckRestart")]
+    #[serde(default)]
+    pub check_restart: Option<CheckRestart>,
+    /// Task running `script` checks of group services.
+    #[serde(rename = "TaskName")]
+    #[serde(default)]
+    pub task_name: String,
+    #[serde(rename = "OnUpdate")]
+    #[serde(default)]
+    pub on_update: String,
+}
+
+impl ServiceCheck {
+    /// `GET` on `path` of the service port.
+    pub fn http(path: impl Into<String>, interval: Duration, timeout: Duration) -> Self {
+        ServiceCheck {
+            path: path.into(),
+            ..ServiceCheck::new(CheckType::Http, interval, timeout)
+        }
+    }
+
+    /// TCP connection to the service port.
+    pub fn tcp(interval: Duration, timeout: Duration) -> Self {
+        ServiceCheck::new(CheckType::Tcp, interval, timeout)
+    }
+
+    /// gRPC health check of `grpc_service`, every service of the server if empty.
+    pub fn grpc(grpc_service: impl Into<String>, interval: Duration, timeout: Duration) -> Self {
+        ServiceCheck {
+            grpc_service: grpc_service.into(),
+            ..ServiceCheck::new(CheckType::Grpc, interval, timeout)
+        }
+    }
+
+    /// `command` run inside the task, healthy if it exits with 0.
+    pub fn script(
+        command: impl Into<String>,
+        args: Vec<String>,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Self {
+        ServiceCheck {
+            command: command.into(),
+            args: Some(args),
+            ..ServiceCheck::new(CheckType::Script, interval, timeout)
+        }
+    }
+
+    fn new(type_field: CheckType, interval: Duration, timeout: Duration) -> Self {
+        ServiceCheck {
+            type_field,
+            interval: interval.as_nanos() as i64,
+            timeout: timeout.as_nanos() as i64,
+            ..ServiceCheck::default()
+        }
+    }
+}
+
+wire_enum! {
+    CheckType {
+        Http => "http",
+        Tcp => "tcp",
+        Grpc => "grpc",
+        Script => "script",
+    }
+    default: Http,
+}
+
+/// Restarts the task when its check stays unhealthy.
+///
+/// https://www.nomadproject.io/docs/job-specification/check_restart
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckRestart {
+    /// Consecutive failed checks before restarting, 0 never restarts.
+    #[serde(rename = "Limit")]
+    #[serde(default)]
+    pub limit: i64,
+    /// Nanoseconds after a start before failures count.
+    #[serde(rename = "Grace")]
+    #[serde(default)]
+    pub grace: Option<i64>,
+    #[serde(rename = "IgnoreWarnings")]
+    #[serde(default)]
+    pub ignore_warnings: bool,
+}
+
+/// https://www.nomadproject.io/docs/job-specification/connect
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsulConnect {
+    /// The task speaks Connect natively and needs no sidecar.
+    #[serde(rename = "Native")]
+    #[serde(default)]
+    pub native: bool,
+    #[serde(rename = "SidecarService")]
+    #[serde(default)]
+    pub sidecar_service: Option<ConsulSidecarService>,
+    /// Overrides of the Envoy task Nomad injects.
+    #[serde(rename = "SidecarTask")]
+    #[serde(default)]
+    pub sidecar_task: Option<::serde_json::Value>,
+    #[serde(rename = "Gateway")]
+    #[serde(default)]
+    pub gateway: Option<::serde_json::Value>,
+}
+
+/// https://www.nomadproject.io/docs/job-specification/sidecar_service
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsulSidecarService {
+    #[serde(rename = "Tags")]
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "Port")]
+    #[serde(default)]
+    pub port: String,
+    #[serde(rename = "Proxy")]
+    #[serde(default)]
+    pub proxy: Option<ConsulProxy>,
+    #[serde(rename = "DisableDefaultTCPCheck")]
+    #[serde(default)]
+    pub disable_default_tcp_check: bool,
+    #[serde(rename = "Meta")]
+    #[serde(default)]
+    pub meta: Option<HashMap<String, String>>,
+}
+
+/// https://www.nomadproject.io/docs/job-specification/proxy
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsulProxy {
+    #[serde(rename = "LocalServiceAddress")]
+    #[serde(default)]
+    pub local_service_address: String,
+    #[serde(rename = "LocalServicePort")]
+    #[serde(default)]
+    pub local_service_port: i64,
+    #[serde(rename = "Upstreams")]
+    #[serde(default)]
+    pub upstreams: Option<Vec<ConsulUpstream>>,
+    #[serde(rename = "Expose")]
+    #[serde(default)]
+    pub expose: Option<::serde_json::Value>,
+    /// Envoy configuration passed through to Consul.
+    #[serde(rename = "Config")]
+    #[serde(default)]
+    pub config: Option<HashMap<String, ::serde_json::Value>>,
+}
+
+/// https://www.nomadproject.io/docs/job-specification/upstreams
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsulUpstream {
+    #[serde(rename = "DestinationName")]
+    pub destination_name: String,
+    #[serde(rename = "DestinationNamespace")]
+    #[serde(default)]
+    pub destination_namespace: String,
+    #[serde(rename = "LocalBindPort")]
+    pub local_bind_port: i64,
+    #[serde(rename = "LocalBindAddress")]
+    #[serde(default)]
+    pub local_bind_address: String,
+    #[serde(rename = "Datacenter")]
+    #[serde(default)]
+    pub datacenter: String,
+    #[serde(rename = "MeshGateway")]
+    #[serde(default)]
+    pub mesh_gateway: Option<::serde_json::Value>,
+}
@@ -0,0 +1,156 @@
+//! Statuses reported by Nomad for jobs, allocations, tasks, evaluations and deployments.
+//!
+//! Each status is a separate enum so a job status cannot be compared with an allocation status
+//! by mistake. Values unknown to this crate, e.g. added by a newer Nomad, are kept in an
+//! `Other` variant instead of failing deserialization.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+macro_rules! status {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)+
+        }
+        default: $default:ident,
+        terminal: [$($terminal:ident),*],
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// A status this crate does not know about.
+            Other(String),
+        }
+
+        impl $name {
+            /// The status as Nomad spells it.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Other(value) => value,
+                }
+            }
+
+            /// Whether the status is final. Statuses in `Other` are not.
+            pub fn is_terminal(&self) -> bool {
+                matches!(self, $($name::$terminal)|*)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::$default
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)+
+                    value => $name::Other(value.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+status! {
+    /// `Status` of a job.
+    JobStatus {
+        Pending => "pending",
+        Running => "running",
+        /// Every allocation stopped, or the job was stopped.
+        Dead => "dead",
+    }
+    default: Pending,
+    terminal: [Dead],
+}
+
+status! {
+    /// `ClientStatus` of an allocation, as reported by the client running it.
+    AllocClientStatus {
+        Pending => "pending",
+        Running => "running",
+        Complete => "complete",
+        Failed => "failed",
+        Lost => "lost",
+        /// The client disconnected and the allocation may still be running.
+        Unknown => "unknown",
+    }
+    default: Pending,
+    terminal: [Complete, Failed, Lost],
+}
+
+status! {
+    /// `DesiredStatus` of an allocation, as decided by the scheduler.
+    AllocDesiredStatus {
+        Run => "run",
+        Stop => "stop",
+        Evict => "evict",
+    }
+    default: Run,
+    terminal: [Stop, Evict],
+}
+
+status! {
+    /// `State` of a task in an allocation.
+    TaskStateKind {
+        Pending => "pending",
+        Running => "running",
+        Dead => "dead",
+    }
+    default: Pending,
+    terminal: [Dead],
+}
+
+status! {
+    /// `Status` of an evaluation.
+    EvalStatus {
+        /// Waiting for resources to free up.
+        Blocked => "blocked",
+        Pending => "pending",
+        Complete => "complete",
+        Failed => "failed",
+        Canceled => "canceled",
+    }
+    default: Pending,
+    terminal: [Complete, Failed, Canceled],
+}
+
+status! {
+    /// `Status` of a deployment.
+    DeploymentStatus {
+        Initializing => "initializing",
+        Running => "running",
+        Pending => "pending",
+        Blocked => "blocked",
+        Unblocking => "unblocking",
+        Paused => "paused",
+        Failed => "failed",
+        Successful => "successful",
+        Cancelled => "cancelled",
+    }
+    default: Running,
+    terminal: [Failed, Successful, Cancelled],
+}
@@ -0,0 +1,287 @@
+use crate::error::NomadError;
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerName};
+use std::convert::TryFrom;
+use std::env::var;
+use std::fmt;
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// TLS settings of a [`crate::client::NomadClient`], mirroring the `-ca-cert`, `-ca-path`,
+/// `-client-cert`, `-client-key`, `-tls-server-name` and `-tls-skip-verify` flags of the Nomad
+/// CLI.
+///
+/// As with the CLI, configuring a CA certificate or CA directory replaces the built-in root
+/// store instead of extending it. Certificates and keys can also be given as PEM bytes, for
+/// instance when they are issued by Vault and never written to disk.
+///
+/// Server certificates are always verified unless `insecure` is set explicitly.
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    /// PEM file with the CA certificate(s) used to verify the Nomad server.
+    pub ca_cert: Option<PathBuf>,
+    /// PEM encoded CA certificate(s), in addition to `ca_cert` and `ca_path`.
+    pub ca_cert_pem: Option<Vec<u8>>,
+    /// Directory of PEM files with CA certificates used to verify the Nomad server.
+    pub ca_path: Option<PathBuf>,
+    /// PEM file with the client certificate presented to Nomad.
+    pub client_cert: Option<PathBuf>,
+    /// PEM encoded client certificate, used when `client_cert` is not set.
+    pub client_cert_pem: Option<Vec<u8>>,
+    /// PEM file with the private key of the client certificate.
+    pub client_key: Option<PathBuf>,
+    /// PEM encoded private key of the client certificate, used when `client_key` is not set.
+    pub client_key_pem: Option<Vec<u8>>,
+    /// Name the server certificate is verified against instead of the host of the address,
+    /// e.g. `server.global.nomad`.
+    pub server_name: Option<String>,
+    /// Disables verification of the server certificate.
+    pub insecure: bool,
+}
+
+impl TlsConfig {
+    /// Reads `NOMAD_CACERT`, `NOMAD_CAPATH`, `NOMAD_CLIENT_CERT`, `NOMAD_CLIENT_KEY`,
+    /// `NOMAD_TLS_SERVER_NAME` and `NOMAD_SKIP_VERIFY`.
+    ///
+    /// Like the CLI, empty variables are ignored and a `NOMAD_SKIP_VERIFY` that is not a
+    /// boolean is ignored.
+    pub fn from_env() -> Self {
+        TlsConfig {
+            ca_cert: env("NOMAD_CACERT").map(PathBuf::from),
+            ca_path: env("NOMAD_CAPATH").map(PathBuf::from),
+            client_cert: env("NOMAD_CLIENT_CERT").map(PathBuf::from),
+            client_key: env("NOMAD_CLIENT_KEY").map(PathBuf::from),
+            server_name: env("NOMAD_TLS_SERVER_NAME"),
+            insecure: env("NOMAD_SKIP_VERIFY")
+                .and_then(|v| parse_bool(&v))
+                .unwrap_or(false),
+            ..TlsConfig::default()
+        }
+    }
+
+    /// Whether any setting differs from the defaults of the HTTP client.
+    pub fn is_configured(&self) -> bool {
+        self.has_ca()
+            || self.client_cert.is_some()
+            || self.client_cert_pem.is_some()
+            || self.client_key.is_some()
+            || self.client_key_pem.is_some()
+            || self.server_name.is_some()
+            || self.insecure
+    }
+
+    fn has_ca(&self) -> bool {
+        self.ca_cert.is_some() || self.ca_cert_pem.is_some() || self.ca_path.is_some()
+    }
+
+    /// Builds the rustls configuration described by these settings.
+    pub(crate) fn client_config(&self) -> Result<ClientConfig, NomadError> {
+        let mut roots = RootCertStore::empty();
+        if !self.has_ca() {
+            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject,
+                    ta.spki,
+                    ta.name_constraints,
+                )
+            }));
+        }
+        if let Some(ca_cert) = &self.ca_cert {
+            add_ca_file(&mut roots, ca_cert)?;
+        }
+        if let Some(pem) = &self.ca_cert_pem {
+            add_ca(&mut roots, pem, "CA certificate PEM")?;
+        }
+        if let Some(ca_path) = &self.ca_path {
+            let entries = fs::read_dir(ca_path).map_err(|e| {
+                NomadError::Config(format!("cannot read CA path {}: {}", ca_path.display(), e))
+            })?;
+            for entry in entries {
+                let path = entry
+                    .map_err(|e| {
+                        NomadError::Config(format!(
+                            "cannot read CA path {}: {}",
+                            ca_path.display(),
+                            e
+                        ))
+                    })?
+                    .path();
+                if path.is_file() {
+                    add_ca_file(&mut roots, &path)?;
+                }
+            }
+        }
+
+        let verifier: Arc<dyn ServerCertVerifier> = if self.insecure {
+            Arc::new(NoVerification)
+        } else {
+            let webpki = WebPkiVerifier::new(roots, None);
+            match &self.server_name {
+                Some(name) => Arc::new(ServerNameOverride {
+                    inner: webpki,
+                    server_name: ServerName::try_from(name.as_str()).map_err(|_| {
+                        NomadError::Config(format!("invalid TLS server name {}", name))
+                    })?,
+                }),
+                None => Arc::new(webpki),
+            }
+        };
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(verifier);
+        let cert = match (&self.client_cert, &self.client_cert_pem) {
+            (Some(path), _) => Some(read_certs(&read_file(path)?, &path.display().to_string())?),
+            (None, Some(pem)) => Some(read_certs(pem, "client certificate PEM")?),
+            (None, None) => None,
+        };
+        let key = match (&self.client_key, &self.client_key_pem) {
+            (Some(path), _) => Some(read_key(&read_file(path)?, &path.display().to_string())?),
+            (None, Some(pem)) => Some(read_key(pem, "client key PEM")?),
+            (None, None) => None,
+        };
+        match (cert, key) {
+            (Some(cert), Some(key)) => builder
+                .with_client_auth_cert(cert, key)
+                .map_err(|e| NomadError::Config(format!("invalid client certificate: {}", e))),
+            (None, None) => Ok(builder.with_no_client_auth()),
+            _ => Err(NomadError::Config(
+                "client certificate and client key must be set together".to_string(),
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("ca_cert", &self.ca_cert)
+            .field("ca_cert_pem", &self.ca_cert_pem.as_ref().map(|_| "<pem>"))
+            .field("ca_path", &self.ca_path)
+            .field("client_cert", &self.client_cert)
+            .field(
+                "client_cert_pem",
+                &self.client_cert_pem.as_ref().map(|_| "<pem>"),
+            )
+            .field("client_key", &self.client_key)
+            .field(
+                "client_key_pem",
+                &self.client_key_pem.as_ref().map(|_| "<redacted>"),
+            )
+            .field("server_name", &self.server_name)
+            .field("insecure", &self.insecure)
+            .finish()
+    }
+}
+
+/// Reads an environment variable, treating an empty value as unset.
+pub(crate) fn env(name: &str) -> Option<String> {
+    var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// Parses a boolean the way Go's `strconv.ParseBool` does.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "1" | "t" | "T" | "true" | "TRUE" | "True" => Some(true),
+        "0" | "f" | "F" | "false" | "FALSE" | "False" => Some(false),
+        _ => None,
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, NomadError> {
+    fs::read(path).map_err(|e| NomadError::Config(format!("cannot read {}: {}", path.display(), e)))
+}
+
+fn read_pem(pem: &[u8], source: &str) -> Result<Vec<rustls_pemfile::Item>, NomadError> {
+    rustls_pemfile::read_all(&mut BufReader::new(pem))
+        .map_err(|e| NomadError::Config(format!("cannot parse PEM in {}: {}", source, e)))
+}
+
+fn read_certs(pem: &[u8], source: &str) -> Result<Vec<Certificate>, NomadError> {
+    let certs: Vec<Certificate> = read_pem(pem, source)?
+        .into_iter()
+        .filter_map(|item| match item {
+            rustls_pemfile::Item::X509Certificate(der) => Some(Certificate(der)),
+            _ => None,
+        })
+        .collect();
+    if certs.is_empty() {
+        return Err(NomadError::Config(format!(
+            "no certificate found in {}",
+            source
+        )));
+    }
+    Ok(certs)
+}
+
+fn read_key(pem: &[u8], source: &str) -> Result<PrivateKey, NomadError> {
+    read_pem(pem, source)?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::RSAKey(der)
+            | rustls_pemfile::Item::PKCS8Key(der)
+            | rustls_pemfile::Item::ECKey(der) => Some(PrivateKey(der)),
+            _ => None,
+        })
+        .ok_or_else(|| NomadError::Config(format!("no private key found in {}", source)))
+}
+
+fn add_ca_file(roots: &mut RootCertStore, path: &Path) -> Result<(), NomadError> {
+    add_ca(roots, &read_file(path)?, &path.display().to_string())
+}
+
+fn add_ca(roots: &mut RootCertStore, pem: &[u8], source: &str) -> Result<(), NomadError> {
+    for cert in read_certs(pem, source)? {
+        roots.add(&cert).map_err(|e| {
+            NomadError::Config(format!("invalid CA certificate in {}: {}", source, e))
+        })?;
+    }
+    Ok(())
+}
+
+/// Verifies the server certificate against a fixed name rather than the host being dialled.
+struct ServerNameOverride {
+    inner: WebPkiVerifier,
+    server_name: ServerName,
+}
+
+impl ServerCertVerifier for ServerNameOverride {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        _server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            &self.server_name,
+            scts,
+            ocsp_response,
+            now,
+        )
+    }
+}
+
+/// Accepts any server certificate, only installed when `insecure` is set.
+struct NoVerification;
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
@@ -0,0 +1,193 @@
+//! Watchers built on blocking queries, see [`QueryOptions`].
+//!
+//! Each watcher yields the current state of the object first and then a new snapshot every time
+//! its Nomad index advances. Blocking queries that time out without a change are not yielded.
+//! If Nomad reports an index lower than the last one seen (e.g. after a snapshot restore) the
+//! watcher starts over from index 0.
+//!
+//! Retryable errors (see [`NomadError::is_retryable`]) are yielded and the query is retried with
+//! an exponential backoff capped at one minute. Any other error is yielded and ends the stream,
+//! as does an answer without an `X-Nomad-Index`, which cannot be blocked on.
+//! Dropping the stream cancels the pending request.
+//!
+//! `q` is used for every query. Its `index` is the index to start waiting from, leave it unset
+//! to receive the current state first.
+//!
+//! ```no_run
+//! # async fn watch(client: nomad_api::client::NomadClient) {
+//! use futures::StreamExt;
+//! use nomad_api::query::QueryOptions;
+//!
+//! let mut allocation = client.watch_allocation("30cd7ef8-cd5c-e950-2ce5-e512fdcdd551", &QueryOptions::default());
+//! while let Some(allocation) = allocation.next().await {
+//!     println!("{:?}", allocation.map(|a| a.client_status));
+//! }
+//! # }
+//! ```
+
+use crate::client::NomadClient;
+use crate::error::NomadError;
+use crate::jobs::{Allocation, AllocationListStub, Job};
+use crate::query::{QueryMeta, QueryOptions};
+use futures::stream::{self, BoxStream, StreamExt};
+use futures::Future;
+use log::{debug, warn};
+use std::time::Duration;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+impl NomadClient {
+    /// Watches `/v1/job/:job_id`.
+    pub fn watch_job(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> BoxStream<'static, Result<Job, NomadError>> {
+        let client = self.clone();
+        let id = id.to_string();
+        watch(q.clone(), move |q| {
+            let client = client.clone();
+            let id = id.clone();
+            async move { client.read_job(&id, &q).await }
+        })
+    }
+
+    /// Watches `/v1/allocation/:alloc_id`.
+    pub fn watch_allocation(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> BoxStream<'static, Result<Allocation, NomadError>> {
+        let client = self.clone();
+        let id = id.to_string();
+        watch(q.clone(), move |q| {
+            let client = client.clone();
+            let id = id.clone();
+            async move { client.read_allocation(&id, &q).await }
+        })
+    }
+
+    /// Watches `/v1/job/:job_id/allocations`.
+    pub fn watch_job_allocations(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> BoxStream<'static, Result<Vec<AllocationListStub>, NomadError>> {
+        let client = self.clone();
+        let id = id.to_string();
+        watch(q.clone(), move |q| {
+            let client = client.clone();
+            let id = id.clone();
+            async move { client.list_job_allocations(&id, &q).await }
+        })
+    }
+
+    /// Watches `/v1/evaluation/:eval_id/allocations`.
+    pub fn watch_evaluation_allocations(
+        &self,
+        id: &str,
+        q: &QueryOptions,
+    ) -> BoxStream<'static, Result<Vec<AllocationListStub>, NomadError>> {
+        let client = self.clone();
+        let id = id.to_string();
+        watch(q.clone(), move |q| {
+            let client = client.clone();
+            let id = id.clone();
+            async move { client.list_evaluation_allocations(&id, &q).await }
+        })
+    }
+}
+
+struct WatchState<F> {
+    fetch: F,
+    q: QueryOptions,
+    index: u64,
+    seen: bool,
+    failures: u32,
+    done: bool,
+}
+
+/// Repeats blocking queries issued by `fetch`, yielding every snapshot with a new index.
+pub(crate) fn watch<T, F, Fut>(
+    q: QueryOptions,
+    fetch: F,
+) -> BoxStream<'static, Result<T, NomadError>>
+where
+    T: Send + 'static,
+    F: FnMut(QueryOptions) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(T, QueryMeta), NomadError>> + Send,
+{
+    let index = q.index.unwrap_or(0);
+    let state = WatchState {
+        fetch,
+        q,
+        index,
+        seen: index > 0,
+        failures: 0,
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        loop {
+            if state.failures > 0 {
+                tokio::time::sleep(backoff(state.failures)).await;
+            }
+            let mut q = state.q.clone();
+            q.index = if state.index > 0 {
+                Some(state.index)
+            } else {
+                None
+            };
+            match (state.fetch)(q).await {
+                Ok((value, meta)) => {
+                    state.failures = 0;
+                    if meta.last_index == 0 {
+                        // Nomad reports at least index 1, without one every query would return
+                        // right away and the watcher would spin.
+                        state.done = true;
+                        return Some((
+                            Err(NomadError::InvalidRequest(
+                                "blocking query answered without an X-Nomad-Index".to_string(),
+                            )),
+                            state,
+                        ));
+                    }
+                    if meta.last_index < state.index {
+                        debug!(
+                            "Index went back from {} to {}, resetting",
+                            state.index, meta.last_index
+                        );
+                        state.index = 0;
+                        state.seen = false;
+                        continue;
+                    }
+                    if state.seen && meta.last_index == state.index {
+                        continue;
+                    }
+                    state.index = meta.last_index;
+                    state.seen = true;
+                    return Some((Ok(value), state));
+                }
+                Err(e) => {
+                    if e.is_retryable() {
+                        state.failures += 1;
+                        warn!("Blocking query failed ({} in a row): {}", state.failures, e);
+                    } else {
+                        state.done = true;
+                    }
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+pub(crate) fn backoff(failures: u32) -> Duration {
+    MIN_BACKOFF
+        .checked_mul(1 << failures.saturating_sub(1).min(16))
+        .map_or(MAX_BACKOFF, |d| d.min(MAX_BACKOFF))
+}
@@ -0,0 +1,130 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::jobs::{AllocLifecycleResponse, AllocationListOptions};
+    use nomad_api::query::QueryOptions;
+
+    #[tokio::test]
+    async fn stop_allocation() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{"EvalID":"5456bd7a-9fc0-c0dd-6131-cbee77f57577","Index":54}"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let response = client.stop_allocation("a8198d79", true).await.unwrap();
+        assert_eq!(response.eval_id, "5456bd7a-9fc0-c0dd-6131-cbee77f57577");
+
+        let request = server.request();
+        assert_eq!(request.method, "POST");
+        assert_eq!(
+            request.path,
+            "/v1/allocation/a8198d79/stop?no_shutdown_delay=true"
+        );
+    }
+
+    #[tokio::test]
+    async fn restart_and_signal_allocation() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, r#"{"Index":0}"#),
+            MockResponse::json(200, "{}"),
+            MockResponse::json(200, "{}"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+
+        let restarted = client.restart_allocation("a8198d79", None).await.unwrap();
+        assert_eq!(restarted.index, 0);
+        let request = server.request();
+        assert_eq!(request.path, "/v1/client/allocation/a8198d79/restart");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        // Only running tasks are restarted, completed lifecycle tasks are left alone.
+        assert_eq!(body, serde_json::json!({"AllTasks": false}));
+
+        client
+            .restart_allocation("a8198d79", Some("worker"))
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&server.request().body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"TaskName": "worker", "AllTasks": false})
+        );
+
+        let signaled = client
+            .signal_allocation("a8198d79", "SIGUSR1", Some("worker"))
+            .await
+            .unwrap();
+        assert_eq!(signaled, AllocLifecycleResponse::default());
+        let request = server.request();
+        assert_eq!(request.path, "/v1/client/allocation/a8198d79/signal");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"Signal": "SIGUSR1", "Task": "worker"})
+        );
+    }
+
+    #[tokio::test]
+    async fn list_allocations_defaults_send_no_flags() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            &format!("[{}]", stub("a8198d79")),
+        )
+        .header("X-Nomad-Index", "57")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        client
+            .list_allocations(&AllocationListOptions::default(), &QueryOptions::default())
+            .await
+            .unwrap();
+        let request = server.request();
+        assert!(!request.path.contains("resources"));
+        assert!(!request.path.contains("task_states"));
+    }
+
+    fn stub(id: &str) -> String {
+        format!(
+            r#"{{"ID":"{}","EvalID":"5456bd7a","Name":"numpire-worker-gpu/dispatch-1692361800-4f0c6a3e.worker[0]","Namespace":"numpire","NodeID":"f7476465","NodeName":"gpu-worker-1","JobID":"numpire-worker-gpu/dispatch-1692361800-4f0c6a3e","JobType":"batch","JobVersion":0,"TaskGroup":"worker","AllocatedResources":null,"DesiredStatus":"run","DesiredDescription":"","ClientStatus":"running","ClientDescription":"Tasks are running","DesiredTransition":{{}},"TaskStates":null,"DeploymentStatus":null,"FollowupEvalID":"","RescheduleTracker":null,"PreemptedAllocations":null,"PreemptedByAllocation":"","CreateIndex":54,"ModifyIndex":57,"CreateTime":1692361800000000000,"ModifyTime":1692361801000000000}}"#,
+            id
+        )
+    }
+
+    #[tokio::test]
+    async fn list_allocations_follows_next_token() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &format!("[{}]", stub("a8198d79")))
+                .header("X-Nomad-Index", "57")
+                .header("X-Nomad-NextToken", "b1c2d3e4"),
+            MockResponse::json(200, &format!("[{}]", stub("b1c2d3e4")))
+                .header("X-Nomad-Index", "57"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let q = QueryOptions {
+            namespace: Some("*".to_string()),
+            per_page: Some(1),
+            index: Some(50),
+            ..QueryOptions::default()
+        };
+        let options = AllocationListOptions {
+            resources: true,
+            task_states: false,
+        };
+        let (allocations, meta) = client.list_allocations(&options, &q).await.unwrap();
+        assert_eq!(allocations.len(), 2);
+        assert_eq!(allocations[1].id, "b1c2d3e4");
+        assert_eq!(meta.last_index, 57);
+        assert_eq!(meta.next_token, None);
+
+        let first = server.request();
+        assert!(first.path.contains("index=50"));
+        assert!(first.path.contains("namespace=*") || first.path.contains("namespace=%2A"));
+        assert!(first.path.contains("resources=true"));
+        assert!(first.path.contains("task_states=false"));
+        let second = server.request();
+        assert!(second.path.contains("next_token=b1c2d3e4"));
+        assert!(second.path.contains("resources=true"));
+        assert!(second.path.contains("task_states=false"));
+        assert!(!second.path.contains("index=50"));
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn read_allocation_stats() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "ResourceUsage": {
+                    "MemoryStats": {"RSS": 6664192, "Cache": 1052672, "Swap": 0, "Usage": 7716864, "MaxUsage": 9113600, "Measured": ["RSS", "Cache", "Swap", "Usage", "Max Usage"]},
+                    "CpuStats": {"SystemMode": 0.5, "UserMode": 98.2, "TotalTicks": 2831.7, "ThrottledPeriods": 0, "ThrottledTime": 0, "Percent": 98.7, "Measured": ["System Mode", "User Mode", "Percent"]},
+                    "DeviceStats": null
+                },
+                "Tasks": {
+                    "worker": {
+                        "Pids": null,
+                        "ResourceUsage": {
+                            "MemoryStats": {"RSS": 6664192, "Cache": 1052672, "Swap": 0, "Usage": 7716864, "MaxUsage": 9113600, "Measured": ["RSS"]},
+                            "CpuStats": {"SystemMode": 0.5, "UserMode": 98.2, "TotalTicks": 2831.7, "ThrottledPeriods": 0, "ThrottledTime": 0, "Percent": 98.7, "Measured": ["Percent"]},
+                            "DeviceStats": [
+                                {"Vendor": "nvidia", "Type": "gpu", "Name": "Tesla T4", "InstanceStats": {
+                                    "GPU-f8c1a2bb-5b34-2d86-bd0f-3bd1f8fa16e1": {
+                                        "Summary": {"IntNumeratorVal": 512, "IntDenominatorVal": 15109, "Unit": "MiB", "Desc": "UsedMemory / TotalMemory"},
+                                        "Stats": {"Attributes": {}, "Nested": {}},
+                                        "Timestamp": "2023-08-18T12:30:00Z"
+                                    }
+                                }}
+                            ]
+                        },
+                        "Timestamp": 1692361800000000000
+                    }
+                },
+                "Timestamp": 1692361800000000000
+            }"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let usage = client
+            .read_allocation_stats("5456bd7a-9fc0-c0dd-6131-cbee77f57577")
+            .await
+            .unwrap();
+        let worker = usage.tasks["worker"].resource_usage.as_ref().unwrap();
+        assert_eq!(worker.memory_stats.as_ref().unwrap().rss, 6664192);
+        assert_eq!(worker.cpu_stats.as_ref().unwrap().total_ticks, 2831.7);
+        let gpu = &worker.device_stats.as_ref().unwrap()[0];
+        let summary = gpu.instance_stats.as_ref().unwrap()
+            ["GPU-f8c1a2bb-5b34-2d86-bd0f-3bd1f8fa16e1"]
+            .summary
+            .as_ref()
+            .unwrap();
+        assert_eq!(summary.int_numerator_val, Some(512));
+        assert_eq!(
+            server.request().path,
+            "/v1/client/allocation/5456bd7a-9fc0-c0dd-6131-cbee77f57577/stats"
+        );
+    }
+
+    #[tokio::test]
+    async fn update_client_metadata() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{"Meta": {"rack": "r2"}, "Dynamic": {"rack": "r2", "maintenance": null}, "Static": {"rack": "r1", "maintenance": "true"}}"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut meta = HashMap::new();
+        meta.insert("rack".to_string(), Some("r2".to_string()));
+        meta.insert("maintenance".to_string(), None);
+        let response = client
+            .update_client_metadata(Some("f7476465"), meta)
+            .await
+            .unwrap();
+        assert_eq!(response.meta["rack"], "r2");
+        assert_eq!(response.dynamic["maintenance"], None);
+        assert_eq!(response.static_meta["rack"], "r1");
+
+        let request = server.request();
+        assert_eq!(request.method, "POST");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["NodeID"], "f7476465");
+        assert_eq!(body["Meta"]["maintenance"], serde_json::Value::Null);
+    }
+}
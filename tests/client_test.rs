@@ -1,14 +1,15 @@
 #[cfg(test)]
 mod tests {
     use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
     use std::collections::HashMap;
 
     #[tokio::test]
     async fn list_jobs() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
-        let list_nodes_response = client.list_jobs().await;
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
+        let list_nodes_response = client.list_jobs(&QueryOptions::default()).await;
         println!("{:?}", &list_nodes_response);
-        for job in list_nodes_response.unwrap() {
+        for job in list_nodes_response.unwrap().0 {
             println!("Stopping job with id {}", &job.id);
             client.stop_job(&job.id).await.unwrap();
         }
@@ -16,18 +17,24 @@ mod tests {
     }
     #[tokio::test]
     async fn list_job_allocations() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let list_job_allocations_response = client
-            .list_job_allocations("numpire-worker-gpu/dispatch-1626361554-dc6a23ac")
+            .list_job_allocations(
+                "numpire-worker-gpu/dispatch-1626361554-dc6a23ac",
+                &QueryOptions::default(),
+            )
             .await;
         println!("{:?}", &list_job_allocations_response);
         list_job_allocations_response.unwrap();
     }
     #[tokio::test]
     async fn read_allocation() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let allocation = client
-            .read_allocation("30cd7ef8-cd5c-e950-2ce5-e512fdcdd551")
+            .read_allocation(
+                "30cd7ef8-cd5c-e950-2ce5-e512fdcdd551",
+                &QueryOptions::default(),
+            )
             .await;
         println!("{:?}", &allocation);
         allocation.unwrap();
@@ -35,7 +42,7 @@ mod tests {
 
     #[tokio::test]
     async fn dispatch_job() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let payload = None;
         let mut meta = HashMap::new();
         meta.insert(
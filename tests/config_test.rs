@@ -0,0 +1,125 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use nomad_api::query::QueryOptions;
+    use std::env;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Serializes the tests touching the process environment.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Sets environment variables and restores their previous values when dropped, even if the
+    /// test panics.
+    struct EnvGuard {
+        saved: Vec<(&'static str, Option<OsString>)>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn new() -> Self {
+            EnvGuard {
+                saved: Vec::new(),
+                _lock: ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner()),
+            }
+        }
+
+        fn set(&mut self, name: &'static str, value: impl AsRef<std::ffi::OsStr>) {
+            if !self.saved.iter().any(|(saved, _)| *saved == name) {
+                self.saved.push((name, env::var_os(name)));
+            }
+            env::set_var(name, value);
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (name, value) in self.saved.drain(..).rev() {
+                match value {
+                    Some(value) => env::set_var(name, value),
+                    None => env::remove_var(name),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn from_env_applies_region_namespace_and_token() {
+        let server = MockNomad::start(vec![MockResponse::json(200, "[]")]);
+        let client = {
+            let mut vars = EnvGuard::new();
+            vars.set("NOMAD_ADDR", format!("{}/", server.url));
+            vars.set("NOMAD_TOKEN", "4c5e8d3e");
+            vars.set("NOMAD_REGION", "eu-west");
+            vars.set("NOMAD_NAMESPACE", "numpire");
+            vars.set("NOMAD_SKIP_VERIFY", "not-a-bool");
+            NomadClient::from_env().unwrap()
+        };
+
+        let (jobs, _) = client.list_jobs(&QueryOptions::default()).await.unwrap();
+        assert!(jobs.is_empty());
+        let request = server.request();
+        assert_eq!(request.path, "/v1/jobs?region=eu-west&namespace=numpire");
+        assert_eq!(request.header("X-Nomad-Token"), Some("4c5e8d3e"));
+
+        let result = {
+            let mut vars = EnvGuard::new();
+            vars.set("NOMAD_CACERT", "/does/not/exist/ca.pem");
+            NomadClient::new(server.url.clone(), "".to_string())
+        };
+        assert!(matches!(result, Err(NomadError::Config(_))));
+    }
+
+    #[test]
+    fn unreadable_ca_cert_is_a_config_error() {
+        let result = NomadClient::builder()
+            .address("https://127.0.0.1:4646")
+            .ca_cert("/does/not/exist/ca.pem")
+            .build();
+        assert!(matches!(result, Err(NomadError::Config(_))));
+    }
+
+    #[test]
+    fn mutual_tls_from_files() {
+        let result = NomadClient::builder()
+            .address("https://127.0.0.1:4646")
+            .ca_cert("tests/assets/tls/ca.pem")
+            .client_cert("tests/assets/tls/cli.pem")
+            .client_key("tests/assets/tls/cli-key.pem")
+            .tls_server_name("server.global.nomad")
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mutual_tls_from_pem() {
+        let ca = fs::read("tests/assets/tls/ca.pem").unwrap();
+        let cert = fs::read("tests/assets/tls/cli.pem").unwrap();
+        let key = fs::read("tests/assets/tls/cli-key.pem").unwrap();
+        let result = NomadClient::builder()
+            .address("https://127.0.0.1:4646")
+            .ca_cert_pem(ca)
+            .client_identity_pem(cert.clone(), b"not a key".to_vec())
+            .build();
+        assert!(matches!(result, Err(NomadError::Config(_))));
+
+        let result = NomadClient::builder()
+            .address("https://127.0.0.1:4646")
+            .client_identity_pem(cert, key)
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn client_cert_requires_key() {
+        let result = NomadClient::builder()
+            .client_cert("/does/not/exist/cli.pem")
+            .build();
+        assert!(matches!(result, Err(NomadError::Config(_))));
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use nomad_api::constraints::{Affinity, Constraint, Operand, Spread};
+    use nomad_api::jobs::Job;
+
+    #[test]
+    fn builders_serialize_like_nomad() {
+        let constraints = vec![
+            Constraint::attr("${attr.kernel.name}").eq("linux"),
+            Constraint::attr("${device.attr.memory}").ge("40 GiB"),
+            Constraint::attr("${attr.unique.hostname}").set_contains_any(["gpu-1", "gpu-2"]),
+            Constraint::attr("${meta.maintenance}").is_not_set(),
+            Constraint::distinct_property("${meta.rack}", Some(2)),
+        ];
+        assert_eq!(
+            serde_json::to_value(&constraints).unwrap(),
+            serde_json::json!([
+                {"LTarget": "${attr.kernel.name}", "RTarget": "linux", "Operand": "="},
+                {"LTarget": "${device.attr.memory}", "RTarget": "40 GiB", "Operand": ">="},
+                {"LTarget": "${attr.unique.hostname}", "RTarget": "gpu-1,gpu-2", "Operand": "set_contains_any"},
+                {"LTarget": "${meta.maintenance}", "RTarget": "", "Operand": "is_not_set"},
+                {"LTarget": "${meta.rack}", "RTarget": "2", "Operand": "distinct_property"}
+            ])
+        );
+
+        let affinity = Affinity::attr("${node.class}").eq("a100").weight(-20);
+        assert_eq!(
+            serde_json::to_value(&affinity).unwrap(),
+            serde_json::json!({"LTarget": "${node.class}", "RTarget": "a100", "Operand": "=", "Weight": -20})
+        );
+
+        let spread = Spread::new("${node.datacenter}").target("eu-west-1", 70);
+        assert_eq!(
+            serde_json::to_value(&spread).unwrap(),
+            serde_json::json!({
+                "Attribute": "${node.datacenter}",
+                "Weight": 50,
+                "SpreadTarget": [{"Value": "eu-west-1", "Percent": 70}]
+            })
+        );
+    }
+
+    #[test]
+    fn job_constraints_are_typed() {
+        let job: Job =
+            serde_json::from_str(&std::fs::read_to_string("tests/assets/job.json").unwrap())
+                .unwrap();
+        assert_eq!(
+            job.constraints.unwrap(),
+            vec![Constraint::attr("${attr.kernel.name}").eq("linux")]
+        );
+        let group = &job.task_groups.unwrap()[0];
+        let constraint = &group.constraints.as_ref().unwrap()[0];
+        assert_eq!(constraint.l_target, "${meta.gpu}");
+        assert_eq!(constraint.operand, Operand::Equal);
+
+        let aliased: Constraint = serde_json::from_str(
+            r#"{"LTarget": "${meta.gpu}", "RTarget": "true", "Operand": "=="}"#,
+        )
+        .unwrap();
+        assert_eq!(aliased.operand, Operand::Equal);
+    }
+
+    #[test]
+    fn unknown_operand_is_kept() {
+        let constraint: Constraint = serde_json::from_str(
+            r#"{"LTarget": "${attr.cpu.numcores}", "RTarget": "8", "Operand": "between"}"#,
+        )
+        .unwrap();
+        assert_eq!(constraint.operand, Operand::Other("between".to_string()));
+        assert_eq!(
+            serde_json::to_value(&constraint).unwrap()["Operand"],
+            "between"
+        );
+        assert_eq!(Operand::from("not"), Operand::NotEqual);
+        assert_eq!(Operand::SetContainsAll.to_string(), "set_contains_all");
+    }
+}
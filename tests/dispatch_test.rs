@@ -0,0 +1,263 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use nomad_api::jobs::{DispatchViolation, DISPATCH_PAYLOAD_LIMIT};
+    use nomad_api::status::{AllocClientStatus, TaskStateKind};
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[derive(Serialize)]
+    struct WorkerMeta {
+        numpire_image: String,
+        gpus: u8,
+        preemptible: bool,
+        priority_class: Option<String>,
+    }
+
+    #[tokio::test]
+    async fn dispatch_raw_payload_with_typed_meta() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "Index": 2051,
+                "JobCreateIndex": 2050,
+                "EvalCreateIndex": 2051,
+                "DispatchedJobID": "numpire-worker-gpu/dispatch-1760781234-3f2a9c1e"
+            }"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let meta = WorkerMeta {
+            numpire_image: "numpire:1.4.2-cuda12".to_string(),
+            gpus: 2,
+            preemptible: false,
+            priority_class: None,
+        };
+        let response = client
+            .dispatch_job_raw(
+                "numpire-worker-gpu",
+                Some(b"{\"grid\": [512, 512]}"),
+                &meta,
+                Some("batch-7731"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.dispatched_job_id,
+            "numpire-worker-gpu/dispatch-1760781234-3f2a9c1e"
+        );
+
+        let request = server.request();
+        assert_eq!(
+            request.path,
+            "/v1/job/numpire-worker-gpu/dispatch?idempotency_token=batch-7731"
+        );
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["Payload"], "eyJncmlkIjogWzUxMiwgNTEyXX0=");
+        assert_eq!(
+            body["Meta"],
+            serde_json::json!({
+                "numpire_image": "numpire:1.4.2-cuda12",
+                "gpus": "2",
+                "preemptible": "false"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_payload_is_rejected_locally() {
+        let client = NomadClient::new("http://127.0.0.1:1".to_string(), "".to_string()).unwrap();
+        let payload = vec![0u8; DISPATCH_PAYLOAD_LIMIT + 1];
+        let err = client
+            .dispatch_job_raw("numpire-worker-gpu", Some(&payload), &(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NomadError::InvalidRequest(_)));
+        assert!(err.to_string().contains("16385 bytes"));
+    }
+
+    #[tokio::test]
+    async fn checked_dispatch_lists_violations() {
+        let job = std::fs::read_to_string("tests/assets/job.json").unwrap();
+        let server = MockNomad::start(vec![MockResponse::json(200, &job)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut meta = HashMap::new();
+        meta.insert("modle", "resnet50");
+        meta.insert("epochs", "3");
+        let err = client
+            .dispatch_job_checked("numpire-worker-gpu", None, &meta, None)
+            .await
+            .unwrap_err();
+        match &err {
+            NomadError::InvalidDispatch { job, violations } => {
+                assert_eq!(job, "numpire-worker-gpu");
+                assert_eq!(
+                    violations,
+                    &vec![
+                        DispatchViolation::PayloadRequired,
+                        DispatchViolation::MissingMeta("model".to_string()),
+                        DispatchViolation::UnknownMeta("modle".to_string()),
+                    ]
+                );
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+        assert_eq!(
+            err.to_string(),
+            "invalid dispatch of numpire-worker-gpu: payload is required, \
+             missing required meta key `model`, unknown meta key `modle`"
+        );
+        assert_eq!(server.request().path, "/v1/job/numpire-worker-gpu");
+    }
+
+    #[tokio::test]
+    async fn checked_dispatch_sends_valid_request() {
+        let job = std::fs::read_to_string("tests/assets/job.json").unwrap();
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &job),
+            MockResponse::json(
+                200,
+                r#"{"Index": 2051, "DispatchedJobID": "numpire-worker-gpu/dispatch-1760781234-3f2a9c1e"}"#,
+            ),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut meta = HashMap::new();
+        meta.insert("model", "resnet50");
+        let response = client
+            .dispatch_job_checked("numpire-worker-gpu", Some(b"{}"), &meta, None)
+            .await
+            .unwrap();
+        assert_eq!(response.index, 2051);
+        server.request();
+        let request = server.request();
+        assert_eq!(request.path, "/v1/job/numpire-worker-gpu/dispatch");
+    }
+
+    const DISPATCHED: &str = r#"{"Index": 3010, "JobCreateIndex": 3009, "EvalCreateIndex": 3010, "EvalID": "c2e5b4a1-93f7-4d62-8e0b-5a7d1f3c9e84", "DispatchedJobID": "numpire-worker-gpu/dispatch-1760790000-77ab01cd"}"#;
+
+    fn event(kind: &str, exit_code: i64, details: &str, fails_task: bool, message: &str) -> String {
+        format!(
+            r#"{{"Type":"{}","Time":1760790060000000000,"Details":{},"DiskLimit":0,"DisplayMessage":"{}","DownloadError":"","DriverError":"","DriverMessage":"","ExitCode":{},"FailedSibling":"","FailsTask":{},"GenericSource":"","KillError":"","KillReason":"","KillTimeout":0,"Message":"","RestartReason":"","SetupError":"","Signal":0,"StartDelay":0,"TaskSignal":"","TaskSignalReason":"","ValidationError":"","VaultError":""}}"#,
+            kind, details, message, exit_code, fails_task
+        )
+    }
+
+    fn allocations(index: u64, client_status: &str, task_states: &str) -> MockResponse {
+        MockResponse::json(
+            200,
+            &format!(
+                r#"[{{"ID":"9f1e7c3a-5b2d-4e8f-a061-3c7d9b2e4f10","EvalID":"c2e5b4a1-93f7-4d62-8e0b-5a7d1f3c9e84","Name":"numpire-worker-gpu/dispatch-1760790000-77ab01cd.worker[0]","Namespace":"numpire","NodeID":"f7476465","NodeName":"gpu-worker-1","JobID":"numpire-worker-gpu/dispatch-1760790000-77ab01cd","JobType":"batch","JobVersion":0,"TaskGroup":"worker","AllocatedResources":null,"DesiredStatus":"run","DesiredDescription":"","ClientStatus":"{}","ClientDescription":null,"DesiredTransition":{{}},"TaskStates":{},"DeploymentStatus":null,"FollowupEvalID":null,"RescheduleTracker":null,"PreemptedAllocations":null,"PreemptedByAllocation":"","CreateIndex":3011,"ModifyIndex":{},"CreateTime":0,"ModifyTime":0}}]"#,
+                client_status, task_states, index
+            ),
+        )
+        .header("X-Nomad-Index", &index.to_string())
+    }
+
+    #[tokio::test]
+    async fn run_dispatched_reports_task_outcomes() {
+        let terminated = event(
+            "Terminated",
+            137,
+            r#"{"exit_code":"137","oom_killed":"true","signal":"0"}"#,
+            false,
+            "Exit Code: 137, Exit Message: OOM Killed",
+        );
+        let not_restarting = event(
+            "Not Restarting",
+            0,
+            r#"{"fails_task":"true"}"#,
+            true,
+            "Error was unrecoverable",
+        );
+        let task_states = format!(
+            r#"{{"worker":{{"State":"dead","Failed":true,"Restarts":0,"LastRestart":null,"StartedAt":"2025-10-18T12:20:01Z","FinishedAt":"2025-10-18T12:21:00Z","TaskHandle":null,"Events":[{},{}]}}}}"#,
+            terminated, not_restarting
+        );
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, DISPATCHED),
+            allocations(3011, "pending", "null"),
+            allocations(3020, "failed", &task_states),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut meta = HashMap::new();
+        meta.insert("numpire_image", "localhost:5000/agridos-worker");
+        let outcome = client
+            .run_dispatched("numpire-worker-gpu", None, &meta, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(outcome.alloc_id, "9f1e7c3a-5b2d-4e8f-a061-3c7d9b2e4f10");
+        assert_eq!(outcome.client_status, AllocClientStatus::Failed);
+        assert!(!outcome.succeeded());
+        let worker = &outcome.tasks["worker"];
+        assert_eq!(worker.state, TaskStateKind::Dead);
+        assert_eq!(worker.exit_code, Some(137));
+        assert!(worker.oom_killed);
+        assert_eq!(
+            worker.failure_reason.as_deref(),
+            Some("Error was unrecoverable")
+        );
+        assert_eq!(worker.finished_at.as_deref(), Some("2025-10-18T12:21:00Z"));
+
+        server.request();
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/dispatch-1760790000-77ab01cd/allocations"
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/dispatch-1760790000-77ab01cd/allocations?index=3011"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_dispatched_waits_for_allocation_of_blocked_dispatch() {
+        // The evaluation of the dispatch is blocked and places nothing, a later evaluation
+        // creates the allocation once capacity frees up.
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, DISPATCHED),
+            MockResponse::json(200, "[]").header("X-Nomad-Index", "3005"),
+            allocations(3011, "running", "null"),
+            allocations(3020, "complete", "null"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let outcome = client
+            .run_dispatched("numpire-worker-gpu", None, &(), Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(outcome.alloc_id, "9f1e7c3a-5b2d-4e8f-a061-3c7d9b2e4f10");
+        assert_eq!(outcome.client_status, AllocClientStatus::Complete);
+        assert!(outcome.succeeded());
+
+        server.request();
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/dispatch-1760790000-77ab01cd/allocations"
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/dispatch-1760790000-77ab01cd/allocations?index=3005"
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/dispatch-1760790000-77ab01cd/allocations?index=3011"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_dispatched_times_out() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, DISPATCHED),
+            allocations(3011, "running", "null"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let err = client
+            .run_dispatched("numpire-worker-gpu", None, &(), Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NomadError::Timeout(_)));
+    }
+}
@@ -0,0 +1,69 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use nomad_api::query::QueryOptions;
+
+    #[tokio::test]
+    async fn permission_denied() {
+        let server = MockNomad::start(vec![MockResponse::json(403, "Permission denied")]);
+        let client = NomadClient::new(server.url.clone(), "secret".to_string()).unwrap();
+        let err = client
+            .list_jobs(&QueryOptions::default())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, NomadError::PermissionDenied { ref body } if body == "Permission denied")
+        );
+        assert!(!err.is_retryable());
+        assert_eq!(server.request().header("X-Nomad-Token"), Some("secret"));
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let server = MockNomad::start(vec![MockResponse::json(404, "alloc not found")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let err = client
+            .read_allocation("unknown", &QueryOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NomadError::NotFound { .. }));
+        assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+        let request = server.request();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/v1/allocation/unknown");
+    }
+
+    #[tokio::test]
+    async fn server_error_is_retryable() {
+        let server = MockNomad::start(vec![MockResponse::json(500, "No cluster leader")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let err = client
+            .list_jobs(&QueryOptions::default())
+            .await
+            .unwrap_err();
+        match &err {
+            NomadError::Http { status, body } => {
+                assert_eq!(status.as_u16(), 500);
+                assert_eq!(body, "No cluster leader");
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn deserialization_reports_field_path() {
+        let body = r#"{"EvalID":"e5f55fac","EvalCreateIndex":"twelve","JobModifyIndex":3}"#;
+        let server = MockNomad::start(vec![MockResponse::json(200, body)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let err = client.stop_job("example").await.unwrap_err();
+        match err {
+            NomadError::Deserialization { path, .. } => assert_eq!(path, "EvalCreateIndex"),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+}
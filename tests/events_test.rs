@@ -0,0 +1,165 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use futures::StreamExt;
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use nomad_api::events::{
+        EventPayload, EventStreamItem, EventStreamOptions, StreamEvent, Topic,
+    };
+
+    fn evaluation_frame(index: u64) -> String {
+        format!(
+            r#"{{"Index":{index},"Events":[{{"Topic":"Evaluation","Type":"EvaluationUpdated","Key":"5456bd7a","Namespace":"default","FilterKeys":["numpire-worker-gpu"],"Index":{index},"Payload":{{"Evaluation":{{"ID":"5456bd7a","Priority":50,"Type":"batch","TriggeredBy":"job-register","Namespace":"default","JobID":"numpire-worker-gpu","Status":"complete","CreateIndex":{index},"ModifyIndex":{index}}}}}}}]}}"#,
+            index = index
+        )
+    }
+
+    #[tokio::test]
+    async fn resumes_after_reconnect_and_reports_gaps() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &format!("{{}}\n{}\n", evaluation_frame(10))),
+            MockResponse::json(200, &format!("{}\n{{}}\n", evaluation_frame(15))),
+            MockResponse::json(403, "Permission denied"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let options = EventStreamOptions::default()
+            .topic(Topic::Evaluation, "numpire-worker-gpu")
+            .namespace("default");
+        let mut events = client.event_stream(&options);
+
+        match events.next().await.unwrap().unwrap() {
+            EventStreamItem::Events { index, events } => {
+                assert_eq!(index, 10);
+                assert_eq!(events[0].topic, Topic::Evaluation);
+                match &events[0].payload {
+                    EventPayload::Evaluation(eval) => assert_eq!(eval.job_id, "numpire-worker-gpu"),
+                    other => panic!("unexpected payload {:?}", other),
+                }
+            }
+            other => panic!("unexpected item {:?}", other),
+        }
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            EventStreamItem::Gap {
+                last_index: 10,
+                resumed_index: 15
+            }
+        );
+        assert!(matches!(
+            events.next().await.unwrap().unwrap(),
+            EventStreamItem::Events { index: 15, .. }
+        ));
+        assert!(matches!(
+            events.next().await,
+            Some(Err(NomadError::PermissionDenied { .. }))
+        ));
+        assert!(events.next().await.is_none());
+
+        assert_eq!(
+            server.request().path,
+            "/v1/event/stream?namespace=default&topic=Evaluation%3Anumpire-worker-gpu"
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/event/stream?namespace=default&index=11&topic=Evaluation%3Anumpire-worker-gpu"
+        );
+    }
+
+    #[tokio::test]
+    async fn filtered_reconnect_reports_possible_gap_only_after_index_jump() {
+        // With a topic filter, Nomad skips every index without a matching event: resuming at 12
+        // after 11 is contiguous, resuming at 40 after 12 may or may not have missed events.
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &format!("{}\n", evaluation_frame(11))),
+            MockResponse::json(200, &format!("{}\n", evaluation_frame(12))),
+            MockResponse::json(200, &format!("{}\n", evaluation_frame(40))),
+            MockResponse::json(403, "Permission denied"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let options = EventStreamOptions::default().topic(Topic::Evaluation, "numpire-worker-gpu");
+        let items: Vec<EventStreamItem> = client
+            .event_stream(&options)
+            .filter_map(|item| async move { item.ok() })
+            .collect()
+            .await;
+        let indexes: Vec<String> = items
+            .iter()
+            .map(|item| match item {
+                EventStreamItem::Events { index, .. } => index.to_string(),
+                EventStreamItem::Gap {
+                    last_index,
+                    resumed_index,
+                } => format!("gap {}..{}", last_index, resumed_index),
+            })
+            .collect();
+        assert_eq!(indexes, vec!["11", "12", "gap 12..40", "40"]);
+    }
+
+    #[test]
+    fn unknown_topic_keeps_its_name() {
+        let topic: Topic = serde_json::from_str(r#""HostVolume""#).unwrap();
+        assert_eq!(topic, Topic::Other("HostVolume".to_string()));
+        assert_eq!(topic.to_string(), "HostVolume");
+        assert_eq!(serde_json::to_string(&topic).unwrap(), r#""HostVolume""#);
+        assert_eq!(Topic::from("ACLToken"), Topic::AclToken);
+    }
+
+    #[test]
+    fn unknown_payload_is_kept() {
+        let event: StreamEvent = serde_json::from_str(
+            r#"{"Topic":"Variable","Type":"VariableUpserted","Key":"nomad/jobs/numpire","Namespace":"default","FilterKeys":null,"Index":42,"Payload":{"Variable":{"Path":"nomad/jobs/numpire","CreateIndex":42}}}"#,
+        )
+        .unwrap();
+        assert_eq!(event.topic, Topic::Other("Variable".to_string()));
+        assert_eq!(
+            event.payload,
+            EventPayload::Other(serde_json::json!({
+                "Variable": {"Path": "nomad/jobs/numpire", "CreateIndex": 42}
+            }))
+        );
+        assert_eq!(
+            serde_json::to_value(&event.payload).unwrap()["Variable"]["Path"],
+            "nomad/jobs/numpire"
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_frame_is_yielded_and_stream_continues() {
+        let bad = r#"{"Index":11,"Events":[{"Topic":"Evaluation","Type":"EvaluationUpdated","Key":"5456bd7a","Index":11,"Payload":{"Evaluation":{"ID":42}}}]}"#;
+        let server = MockNomad::start(vec![
+            MockResponse::json(
+                200,
+                &format!(
+                    "{}\n{}\n{}\n",
+                    evaluation_frame(10),
+                    bad,
+                    evaluation_frame(12)
+                ),
+            ),
+            MockResponse::json(403, "Permission denied"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut events = client.event_stream(&EventStreamOptions::default());
+
+        assert!(matches!(
+            events.next().await.unwrap().unwrap(),
+            EventStreamItem::Events { index: 10, .. }
+        ));
+        assert!(matches!(
+            events.next().await,
+            Some(Err(NomadError::Deserialization { .. }))
+        ));
+        assert!(matches!(
+            events.next().await.unwrap().unwrap(),
+            EventStreamItem::Events { index: 12, .. }
+        ));
+        assert!(matches!(
+            events.next().await,
+            Some(Err(NomadError::PermissionDenied { .. }))
+        ));
+        assert!(events.next().await.is_none());
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::{SinkExt, StreamExt};
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// Accepts one exec session, recording the request URI and the first frame sent by the
+    /// client, then answers with `frames`.
+    #[allow(clippy::result_large_err)]
+    async fn serve(
+        listener: TcpListener,
+        frames: Vec<Message>,
+    ) -> (String, Option<String>, serde_json::Value) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut uri = String::new();
+        let mut token = None;
+        let mut socket =
+            tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+                uri = request.uri().to_string();
+                token = request
+                    .headers()
+                    .get("X-Nomad-Token")
+                    .map(|v| v.to_str().unwrap().to_string());
+                Ok(response)
+            })
+            .await
+            .unwrap();
+        let input = match socket.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("unexpected message {:?}", other),
+        };
+        for frame in frames {
+            socket.send(frame).await.unwrap();
+        }
+        (uri, token, input)
+    }
+
+    #[tokio::test]
+    async fn exec_relays_output_and_exit_code() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                // "GPU 0: Tesla T4\n" on stdout, "warning\n" on stderr.
+                Message::Text(r#"{"stdout":{"data":"R1BVIDA6IFRlc2xhIFQ0Cg=="}}"#.to_string()),
+                Message::Text(r#"{"stderr":{"data":"d2FybmluZwo="}}"#.to_string()),
+                Message::Text(r#"{"stdout":{"close":true}}"#.to_string()),
+                Message::Text(r#"{"exited":true,"result":{"exit_code":3}}"#.to_string()),
+            ],
+        ));
+        let client = NomadClient::new(url, "secret".to_string()).unwrap();
+        let mut session = client
+            .exec(
+                "5456bd7a",
+                "worker",
+                &["nvidia-smi".to_string(), "-L".to_string()],
+                false,
+            )
+            .await
+            .unwrap();
+        session.stdin.write(b"y\n").await.unwrap();
+
+        let stdout: Vec<Bytes> = session.stdout.by_ref().collect().await;
+        let stdout: Vec<u8> = stdout.concat();
+        assert_eq!(stdout, b"GPU 0: Tesla T4\n");
+        assert_eq!(&session.stderr.next().await.unwrap()[..], b"warning\n");
+        assert_eq!(session.wait().await.unwrap(), 3);
+
+        let (uri, token, input) = server.await.unwrap();
+        assert!(uri.starts_with("/v1/client/allocation/5456bd7a/exec?"));
+        assert!(uri.contains("task=worker"));
+        assert!(uri.contains("command=%5B%22nvidia-smi%22%2C%22-L%22%5D"));
+        assert_eq!(token.as_deref(), Some("secret"));
+        assert_eq!(input["stdin"]["data"], "eQo=");
+    }
+
+    #[tokio::test]
+    async fn exec_maps_close_codes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![Message::Close(Some(CloseFrame {
+                code: CloseCode::from(4404),
+                reason: "task not found".into(),
+            }))],
+        ));
+        let client = NomadClient::new(url, "".to_string()).unwrap();
+        let mut session = client
+            .exec("5456bd7a", "missing", &["sh".to_string()], true)
+            .await
+            .unwrap();
+        session.resize(120, 40).await.unwrap();
+        let err = session.wait().await.unwrap_err();
+        assert!(matches!(err, NomadError::NotFound { ref body } if body == "task not found"));
+
+        let (_, _, input) = server.await.unwrap();
+        assert_eq!(input["tty_size"]["width"], 120);
+    }
+}
@@ -0,0 +1,170 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use futures::StreamExt;
+    use nomad_api::client::NomadClient;
+    use nomad_api::fs::{FileEvent, LogType, Origin};
+
+    #[tokio::test]
+    async fn stream_logs_decodes_frames() {
+        // "loading model\n", a heartbeat, a truncation and "epoch 1\n".
+        let body = concat!(
+            r#"{"Offset":14,"Data":"bG9hZGluZyBtb2RlbAo=","File":"alloc/logs/worker.stderr.0"}"#,
+            "\n{}\n",
+            r#"{"Offset":0,"File":"alloc/logs/worker.stderr.0","FileEvent":"file truncated"}"#,
+            r#"{"Offset":8,"Data":"ZXBvY2ggMQo=","File":"alloc/logs/worker.stderr.0"}"#,
+        );
+        let server = MockNomad::start(vec![MockResponse::json(200, body)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let frames: Vec<_> = client
+            .stream_logs(
+                "5456bd7a",
+                "worker",
+                LogType::Stderr,
+                false,
+                Origin::Start,
+                0,
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let frames: Vec<_> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].data, b"loading model\n");
+        assert_eq!(frames[0].offset, 14);
+        assert_eq!(frames[1].file_event, Some(FileEvent::Truncated));
+        assert_eq!(frames[2].data, b"epoch 1\n");
+
+        let request = server.request();
+        assert!(request.path.starts_with("/v1/client/fs/logs/5456bd7a?"));
+        assert!(request.path.contains("task=worker"));
+        assert!(request.path.contains("type=stderr"));
+        assert!(request.path.contains("plain=false"));
+    }
+
+    #[tokio::test]
+    async fn stream_logs_reports_malformed_frames() {
+        let body = r#"{"Offset":14,"Data":"not base64!","File":"alloc/logs/worker.stdout.0"}"#;
+        let server = MockNomad::start(vec![MockResponse::json(200, body)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let frames: Vec<_> = client
+            .stream_logs(
+                "5456bd7a",
+                "worker",
+                LogType::Stdout,
+                false,
+                Origin::End,
+                4096,
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 1);
+        assert!(frames[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn list_and_read_allocation_files() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(
+                200,
+                r#"[
+                    {"Name": "result.json", "IsDir": false, "Size": 17, "FileMode": "-rw-r--r--", "ModTime": "2023-08-18T12:40:05.184423137Z", "ContentType": "application/json"},
+                    {"Name": "checkpoints", "IsDir": true, "Size": 4096, "FileMode": "drwxr-xr-x", "ModTime": "2023-08-18T12:35:00Z", "ContentType": ""}
+                ]"#,
+            ),
+            MockResponse::json(200, r#"{"accuracy":0.93}"#),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let files = client
+            .list_allocation_files("5456bd7a", "alloc/data")
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[1].is_dir);
+        assert_eq!(files[0].content_type, "application/json");
+        assert_eq!(
+            server.request().path,
+            "/v1/client/fs/ls/5456bd7a?path=alloc%2Fdata"
+        );
+
+        let content = client
+            .read_allocation_file_at("5456bd7a", "alloc/data/result.json", 0, 17)
+            .await
+            .unwrap();
+        assert_eq!(&content[..], br#"{"accuracy":0.93}"#);
+        assert_eq!(
+            server.request().path,
+            "/v1/client/fs/readat/5456bd7a?path=alloc%2Fdata%2Fresult.json&offset=0&limit=17"
+        );
+    }
+
+    #[tokio::test]
+    async fn stat_and_read_allocation_file() {
+        let server = MockNomad::start(vec![
+            MockResponse::json(
+                200,
+                r#"{"Name": "result.json", "IsDir": false, "Size": 17, "FileMode": "-rw-r--r--", "ModTime": "2023-08-18T12:40:05.184423137Z", "ContentType": "application/json"}"#,
+            ),
+            MockResponse::json(200, r#"{"accuracy":0.93}"#),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let info = client
+            .stat_allocation_file("5456bd7a", "alloc/data/result.json")
+            .await
+            .unwrap();
+        assert_eq!(info.name, "result.json");
+        assert_eq!(info.size, 17);
+        assert!(!info.is_dir);
+        let request = server.request();
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.path,
+            "/v1/client/fs/stat/5456bd7a?path=alloc%2Fdata%2Fresult.json"
+        );
+
+        let content = client
+            .read_allocation_file("5456bd7a", "alloc/data/result.json")
+            .await
+            .unwrap();
+        assert_eq!(&content[..], br#"{"accuracy":0.93}"#);
+        assert_eq!(
+            server.request().path,
+            "/v1/client/fs/cat/5456bd7a?path=alloc%2Fdata%2Fresult.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_allocation_file_decodes_frames() {
+        // "step 100 loss 0.41\n", a heartbeat and "step 200 loss 0.27\n".
+        let body = concat!(
+            r#"{"Offset":19,"Data":"c3RlcCAxMDAgbG9zcyAwLjQxCg==","File":"alloc/data/train.log"}"#,
+            "\n{}\n",
+            r#"{"Offset":38,"Data":"c3RlcCAyMDAgbG9zcyAwLjI3Cg==","File":"alloc/data/train.log"}"#,
+        );
+        let server = MockNomad::start(vec![MockResponse::json(200, body)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let frames: Vec<_> = client
+            .stream_allocation_file("5456bd7a", "alloc/data/train.log", false, Origin::End, 38)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let frames: Vec<_> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].data, b"step 100 loss 0.41\n");
+        assert_eq!(frames[0].file, "alloc/data/train.log");
+        assert_eq!(frames[1].data, b"step 200 loss 0.27\n");
+        assert_eq!(frames[1].offset, 38);
+        assert_eq!(frames[1].file_event, None);
+
+        assert_eq!(
+            server.request().path,
+            "/v1/client/fs/stream/5456bd7a?path=alloc%2Fdata%2Ftrain.log&follow=false&origin=end&offset=38"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
+
+    #[tokio::test]
+    async fn revert_to_last_stable_version() {
+        let job: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string("tests/assets/job.json").unwrap())
+                .unwrap();
+        let mut previous = job.clone();
+        previous["Version"] = 2.into();
+        previous["Stable"] = true.into();
+        let versions = serde_json::json!({
+            "Versions": [job, previous],
+            "Diffs": [{
+                "Type": "Edited",
+                "ID": "numpire-worker-gpu",
+                "Fields": [{"Type": "Edited", "Name": "Priority", "Old": "50", "New": "70", "Annotations": null}],
+                "Objects": null,
+                "TaskGroups": null
+            }]
+        });
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &versions.to_string()).header("X-Nomad-Index", "1042"),
+            MockResponse::json(
+                200,
+                r#"{"EvalID":"0aa8d8b6-9f4b-5a0c-8e2f-6e3c2b5d1f77","EvalCreateIndex":1060,"JobModifyIndex":1060,"Warnings":"","Index":1060,"LastContact":0,"KnownLeader":false}"#,
+            ),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let (history, _) = client
+            .list_job_versions("numpire-worker-gpu", true, &QueryOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            history.diffs.unwrap()[0].fields.as_ref().unwrap()[0].new,
+            "70"
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/versions?diffs=true"
+        );
+
+        let stable = history
+            .versions
+            .iter()
+            .find(|job| job.stable == Some(true))
+            .unwrap();
+        let current = history.versions[0].version.unwrap() as u64;
+        let response = client
+            .revert_job(
+                "numpire-worker-gpu",
+                stable.version.unwrap() as u64,
+                Some(current),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.job_modify_index, 1060);
+
+        let request = server.request();
+        assert_eq!(request.path, "/v1/job/numpire-worker-gpu/revert");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"JobID": "numpire-worker-gpu", "JobVersion": 2, "EnforcePriorVersion": 3})
+        );
+    }
+}
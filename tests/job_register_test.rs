@@ -0,0 +1,89 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::jobs::JobRegisterOptions;
+    use nomad_api::query::QueryOptions;
+    use nomad_api::status::JobStatus;
+
+    #[tokio::test]
+    async fn read_then_update_with_check_and_set() {
+        let job = std::fs::read_to_string("tests/assets/job.json").unwrap();
+        let server = MockNomad::start(vec![
+            MockResponse::json(200, &job).header("X-Nomad-Index", "1042"),
+            MockResponse::json(
+                200,
+                r#"{"EvalID":"","EvalCreateIndex":0,"JobModifyIndex":1051,"Warnings":"","Index":1051,"LastContact":0,"KnownLeader":false}"#,
+            ),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let (mut job, meta) = client
+            .read_job("numpire-worker-gpu", &QueryOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(meta.last_index, 1042);
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(server.request().path, "/v1/job/numpire-worker-gpu");
+
+        job.priority = 70;
+        let options = JobRegisterOptions {
+            preserve_counts: true,
+            eval_priority: Some(90),
+            ..JobRegisterOptions::check_index(job.job_modify_index as u64)
+        };
+        let response = client.update_job(&job, &options).await.unwrap();
+        assert_eq!(response.job_modify_index, 1051);
+
+        let request = server.request();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/job/numpire-worker-gpu");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["EnforceIndex"], true);
+        assert_eq!(body["JobModifyIndex"], 1042);
+        assert_eq!(body["PreserveCounts"], true);
+        assert_eq!(body["EvalPriority"], 90);
+        assert_eq!(body.get("PolicyOverride"), None);
+        assert_eq!(body["Job"]["Priority"], 70);
+    }
+
+    #[tokio::test]
+    async fn validate_before_register() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{"DriverConfigValidated":false,"ValidationErrors":["Task group worker validation failed: Task worker validation failed: Missing tasks for task group"],"Error":"1 error occurred:\n\t* Task group worker validation failed","Warnings":""}"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let job = serde_json::from_str(&std::fs::read_to_string("tests/assets/job.json").unwrap())
+            .unwrap();
+        let validation = client.validate_job(&job).await.unwrap();
+        assert!(!validation.is_valid());
+        assert_eq!(validation.validation_errors.unwrap().len(), 1);
+
+        let request = server.request();
+        assert_eq!(request.path, "/v1/validate/job");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["Job"]["ID"], "numpire-worker-gpu");
+    }
+
+    #[tokio::test]
+    async fn evaluate_job_with_force_reschedule() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{"EvalID":"d092fdc0-e1fd-2536-67d8-43af8ca798ac","EvalCreateIndex":1070,"JobModifyIndex":1042,"Warnings":"","Index":1070,"LastContact":0,"KnownLeader":false}"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let response = client
+            .evaluate_job("numpire-worker-gpu", true)
+            .await
+            .unwrap();
+        assert_eq!(response.eval_id, "d092fdc0-e1fd-2536-67d8-43af8ca798ac");
+
+        let body: serde_json::Value = serde_json::from_slice(&server.request().body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"JobID": "numpire-worker-gpu", "EvalOptions": {"ForceReschedule": true}})
+        );
+    }
+}
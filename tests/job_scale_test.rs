@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn scale_task_group_targets_group() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "EvalID": "a3b8c9d2-7c1e-4f0a-9b5d-2e6f8a1c4d70",
+                "EvalCreateIndex": 1051,
+                "JobModifyIndex": 1051,
+                "Warnings": "",
+                "Index": 1051,
+                "LastContact": 0,
+                "KnownLeader": false
+            }"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut meta = HashMap::new();
+        meta.insert("queue_depth".to_string(), serde_json::json!(412));
+        let response = client
+            .scale_task_group(
+                "numpire-worker",
+                "worker",
+                Some(6),
+                "queue depth above target",
+                Some(meta),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.eval_create_index, 1051);
+
+        let request = server.request();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/job/numpire-worker/scale");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["Count"], 6);
+        assert_eq!(body["Target"]["Group"], "worker");
+        assert_eq!(body["Meta"]["queue_depth"], 412);
+        assert_eq!(body["PolicyOverride"], false);
+    }
+
+    #[tokio::test]
+    async fn read_scale_status_with_events() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "JobID": "numpire-worker",
+                "Namespace": "numpire",
+                "JobCreateIndex": 12,
+                "JobModifyIndex": 1051,
+                "JobStopped": false,
+                "TaskGroups": {
+                    "worker": {
+                        "Desired": 6,
+                        "Placed": 6,
+                        "Running": 5,
+                        "Healthy": 5,
+                        "Unhealthy": 0,
+                        "Events": [
+                            {
+                                "Count": 6,
+                                "PreviousCount": 4,
+                                "Error": false,
+                                "Message": "queue depth above target",
+                                "Meta": {"queue_depth": 412},
+                                "EvalID": "a3b8c9d2-7c1e-4f0a-9b5d-2e6f8a1c4d70",
+                                "Time": 1760781234000000000,
+                                "CreateIndex": 1051
+                            }
+                        ]
+                    }
+                }
+            }"#,
+        )
+        .header("X-Nomad-Index", "1051")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let (status, meta) = client
+            .read_job_scale_status("numpire-worker", &QueryOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(meta.last_index, 1051);
+        let worker = &status.task_groups["worker"];
+        assert_eq!(worker.desired, 6);
+        assert_eq!(worker.running, 5);
+        let events = worker.events.as_ref().unwrap();
+        assert_eq!(events[0].count, Some(6));
+        assert_eq!(events[0].previous_count, 4);
+        assert_eq!(server.request().path, "/v1/job/numpire-worker/scale");
+    }
+}
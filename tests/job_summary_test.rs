@@ -0,0 +1,37 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
+
+    #[tokio::test]
+    async fn read_parameterized_job_summary() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "JobID": "numpire-worker-gpu",
+                "Namespace": "numpire",
+                "Summary": {
+                    "worker": {"Queued": 0, "Complete": 0, "Failed": 0, "Running": 0, "Starting": 0, "Lost": 0, "Unknown": 0}
+                },
+                "Children": {"Pending": 1284, "Running": 16, "Dead": 20433},
+                "CreateIndex": 12,
+                "ModifyIndex": 1042
+            }"#,
+        )
+        .header("X-Nomad-Index", "1042")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let (summary, meta) = client
+            .read_job_summary("numpire-worker-gpu", &QueryOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(meta.last_index, 1042);
+        assert_eq!(summary.summary["worker"].running, 0);
+        let children = summary.children.unwrap();
+        assert_eq!(children.pending, 1284);
+        assert_eq!(children.running, 16);
+        assert_eq!(server.request().path, "/v1/job/numpire-worker-gpu/summary");
+    }
+}
@@ -1,15 +1,16 @@
 #[cfg(test)]
 mod tests {
     use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
     use std::collections::HashMap;
     use std::path::Path;
 
     #[tokio::test]
     async fn list_jobs() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
-        let list_nodes_response = client.list_jobs().await;
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
+        let list_nodes_response = client.list_jobs(&QueryOptions::default()).await;
         println!("{:?}", &list_nodes_response);
-        for job in list_nodes_response.unwrap() {
+        for job in list_nodes_response.unwrap().0 {
             println!("Stopping job with id {}", &job.id);
             client.stop_job(&job.id).await.unwrap();
         }
@@ -18,9 +19,12 @@ mod tests {
 
     #[tokio::test]
     async fn list_job_allocations() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let list_job_allocations_response = client
-            .list_job_allocations("numpire-worker-gpu/dispatch-1626361554-dc6a23ac")
+            .list_job_allocations(
+                "numpire-worker-gpu/dispatch-1626361554-dc6a23ac",
+                &QueryOptions::default(),
+            )
             .await;
         println!("{:?}", &list_job_allocations_response);
         list_job_allocations_response.unwrap();
@@ -28,9 +32,12 @@ mod tests {
 
     #[tokio::test]
     async fn read_allocation() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let allocation = client
-            .read_allocation("30cd7ef8-cd5c-e950-2ce5-e512fdcdd551")
+            .read_allocation(
+                "30cd7ef8-cd5c-e950-2ce5-e512fdcdd551",
+                &QueryOptions::default(),
+            )
             .await;
         println!("{:?}", &allocation);
         allocation.unwrap();
@@ -38,7 +45,7 @@ mod tests {
 
     #[tokio::test]
     async fn dispatch_job() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let payload = None;
         let mut meta = HashMap::new();
         meta.insert(
@@ -55,7 +62,7 @@ mod tests {
 
     #[tokio::test]
     async fn dispatch_job3() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let payload = None;
         let mut meta = HashMap::new();
         meta.insert(
@@ -69,7 +76,7 @@ mod tests {
 
     // #[tokio::test]
     /*async fn dispatch_job2() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let payload = None;
         let mut meta = HashMap::new();
         meta.insert("blink".to_string(), "127.0.0.1:4444".to_string());
@@ -85,7 +92,7 @@ mod tests {
 
     #[tokio::test]
     async fn parse_job() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let hcl_file =
             std::fs::read_to_string(Path::new("tests/assets/worker-param-gpu.nomad")).unwrap();
         let job = client.parse_job(&hcl_file, true).await;
@@ -95,7 +102,7 @@ mod tests {
 
     #[tokio::test]
     async fn create_job() {
-        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string());
+        let client = NomadClient::new("http://127.0.0.1:4646".to_string(), "".to_string()).unwrap();
         let hcl_file =
             std::fs::read_to_string(Path::new("tests/assets/worker-param-gpu.nomad")).unwrap();
         let job = client.parse_job(&hcl_file, true).await;
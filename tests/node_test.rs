@@ -0,0 +1,61 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::nodes::DrainSpec;
+    use nomad_api::query::QueryOptions;
+
+    #[tokio::test]
+    async fn read_node_with_gpus() {
+        let body = std::fs::read_to_string("tests/assets/node.json").unwrap();
+        let server = MockNomad::start(vec![MockResponse::json(200, &body)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let (node, _) = client
+            .read_node(
+                "f7476465-4d6e-c0de-26d0-e383c49be941",
+                &QueryOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(node.node_pool.as_deref(), Some("default"));
+        assert_eq!(node.attributes["kernel.name"], "linux");
+        let gpus = node.gpus();
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].name, "Tesla T4");
+        assert_eq!(gpus[0].healthy_count(), 1);
+        assert_eq!(
+            gpus[0].attributes.as_ref().unwrap()["memory"].int_val,
+            Some(15109)
+        );
+        assert_eq!(node.drain_strategy.unwrap().deadline, 3_600_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn drain_node() {
+        let server = MockNomad::start(vec![MockResponse::json(
+            200,
+            r#"{"EvalIDs":["253ec083-22a7-76c9-b8b3-4bef6dc6c6e5"],"EvalCreateIndex":1043,"NodeModifyIndex":1043,"Index":1043}"#,
+        )]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let response = client
+            .update_node_drain(
+                "f7476465",
+                Some(DrainSpec {
+                    deadline: 3_600_000_000_000,
+                    ignore_system_jobs: true,
+                }),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.eval_ids.unwrap().len(), 1);
+
+        let request = server.request();
+        assert_eq!(request.path, "/v1/node/f7476465/drain");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["DrainSpec"]["Deadline"], 3_600_000_000_000i64);
+        assert_eq!(body["DrainSpec"]["IgnoreSystemJobs"], true);
+    }
+}
@@ -0,0 +1,122 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::jobs::{DiffType, Job};
+
+    const PLAN: &str = r#"{
+        "JobModifyIndex": 1042,
+        "CreatedEvals": null,
+        "Diff": {
+            "Type": "Edited",
+            "ID": "numpire-worker-gpu",
+            "Fields": [
+                {"Type": "Edited", "Name": "Priority", "Old": "50", "New": "70", "Annotations": null},
+                {"Type": "None", "Name": "Type", "Old": "batch", "New": "batch", "Annotations": null}
+            ],
+            "Objects": null,
+            "TaskGroups": [
+                {
+                    "Type": "Edited",
+                    "Name": "worker",
+                    "Fields": null,
+                    "Objects": null,
+                    "Tasks": [
+                        {
+                            "Type": "Edited",
+                            "Name": "worker",
+                            "Fields": null,
+                            "Objects": [
+                                {
+                                    "Type": "Edited",
+                                    "Name": "Config",
+                                    "Fields": [
+                                        {"Type": "Edited", "Name": "image", "Old": "worker-gpu:1.4.2", "New": "worker-gpu:1.5.0", "Annotations": null}
+                                    ],
+                                    "Objects": null
+                                }
+                            ],
+                            "Annotations": ["forces create/destroy update"]
+                        }
+                    ],
+                    "Updates": {"create/destroy update": 1, "ignore": 0}
+                }
+            ]
+        },
+        "Annotations": {
+            "DesiredTGUpdates": {
+                "worker": {"Ignore": 0, "Place": 0, "Migrate": 0, "Stop": 0, "InPlaceUpdate": 0, "DestructiveUpdate": 1, "Canary": 0, "Preemptions": 0}
+            },
+            "PreemptedAllocs": null
+        },
+        "FailedTGAllocs": {
+            "worker": {
+                "NodesEvaluated": 3,
+                "NodesFiltered": 1,
+                "NodesAvailable": {"eu-west-1a": 3},
+                "ClassFiltered": null,
+                "ConstraintFiltered": {"${meta.gpu} = true": 1},
+                "NodesExhausted": 2,
+                "ClassExhausted": null,
+                "DimensionExhausted": {"devices: nvidia/gpu": 2},
+                "QuotaExhausted": null,
+                "ResourcesExhausted": null,
+                "Scores": null,
+                "ScoreMetaData": null,
+                "AllocationTime": 51923,
+                "CoalescedFailures": 0
+            }
+        },
+        "NextPeriodicLaunch": "0001-01-01T00:00:00Z",
+        "Warnings": ""
+    }"#;
+
+    #[tokio::test]
+    async fn plan_job_renders_diff() {
+        let server = MockNomad::start(vec![MockResponse::json(200, PLAN)]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let job = Job {
+            id: "numpire-worker-gpu".to_string(),
+            priority: 70,
+            ..Job::default()
+        };
+        let plan = client.plan_job(&job, true).await.unwrap();
+        assert_eq!(plan.diff.as_ref().unwrap().type_field, DiffType::Edited);
+        assert_eq!(
+            plan.annotations
+                .as_ref()
+                .unwrap()
+                .desired_tg_updates
+                .as_ref()
+                .unwrap()["worker"]
+                .destructive_update,
+            1
+        );
+        assert_eq!(
+            plan.to_string(),
+            r#"+/- Job: "numpire-worker-gpu"
++/- Priority: "50" => "70"
++/- Task Group: "worker" (1 create/destroy update)
+  +/- Task: "worker" (forces create/destroy update)
+    +/- Config {
+      +/- image: "worker-gpu:1.4.2" => "worker-gpu:1.5.0"
+        }
+
+Scheduler dry-run:
+- WARNING: Failed to place all allocations.
+  Task Group "worker" (failed to place 1 allocation):
+    * Constraint "${meta.gpu} = true": 1 nodes excluded by filter
+    * Resources exhausted on 2 nodes
+    * Dimension "devices: nvidia/gpu" exhausted on 2 nodes
+
+Job Modify Index: 1042"#
+        );
+
+        let request = server.request();
+        assert_eq!(request.path, "/v1/job/numpire-worker-gpu/plan");
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["Diff"], true);
+    }
+}
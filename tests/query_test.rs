@@ -0,0 +1,48 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use nomad_api::client::NomadClient;
+    use nomad_api::query::QueryOptions;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn blocking_query_parameters_and_meta() {
+        let server = MockNomad::start(vec![MockResponse::json(200, "[]")
+            .header("X-Nomad-Index", "1042")
+            .header("X-Nomad-LastContact", "15")
+            .header("X-Nomad-KnownLeader", "true")
+            .header("X-Nomad-NextToken", "numpire-worker-gpu/dispatch-2")]);
+        let client = NomadClient::builder()
+            .address(server.url.clone())
+            .namespace("default")
+            .build()
+            .unwrap();
+        let q = QueryOptions {
+            index: Some(1041),
+            wait: Some(Duration::from_secs(30)),
+            stale: true,
+            namespace: Some("numpire".to_string()),
+            per_page: Some(20),
+            ..QueryOptions::default()
+        };
+
+        let (allocations, meta) = client
+            .list_job_allocations("numpire-worker-gpu", &q)
+            .await
+            .unwrap();
+        assert!(allocations.is_empty());
+        assert_eq!(meta.last_index, 1042);
+        assert_eq!(meta.last_contact, Duration::from_millis(15));
+        assert!(meta.known_leader);
+        assert_eq!(
+            meta.next_token.as_deref(),
+            Some("numpire-worker-gpu/dispatch-2")
+        );
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/allocations?namespace=numpire&index=1041&wait=30000ms&stale=&per_page=20"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use nomad_api::jobs::TaskGroup;
+    use nomad_api::services::{AddressMode, CheckType, Service, ServiceCheck, ServiceProvider};
+    use std::time::Duration;
+
+    #[test]
+    fn read_group_services() {
+        let group: TaskGroup = serde_json::from_str(
+            r#"{
+                "Name": "api",
+                "Count": 2,
+                "Services": [
+                    {
+                        "Name": "numpire-api",
+                        "PortLabel": "8080",
+                        "Tags": ["v1"],
+                        "CanaryTags": ["canary"],
+                        "EnableTagOverride": false,
+                        "Provider": "consul",
+                        "AddressMode": "auto",
+                        "Address": "",
+                        "Checks": [
+                            {
+                                "Name": "health",
+                                "Type": "http",
+                                "Path": "/health",
+                                "Protocol": "http",
+                                "PortLabel": "",
+                                "Expose": true,
+                                "AddressMode": "",
+                                "Interval": 10000000000,
+                                "Timeout": 2000000000,
+                                "CheckRestart": {"Limit": 3, "Grace": 30000000000, "IgnoreWarnings": false},
+                                "OnUpdate": "require_healthy"
+                            }
+                        ],
+                        "Connect": {
+                            "Native": false,
+                            "SidecarService": {
+                                "Tags": null,
+                                "Port": "",
+                                "Proxy": {
+                                    "LocalServiceAddress": "",
+                                    "LocalServicePort": 0,
+                                    "Upstreams": [
+                                        {"DestinationName": "numpire-db", "DestinationNamespace": "", "LocalBindPort": 5432, "Datacenter": "", "LocalBindAddress": "", "MeshGateway": {"Mode": ""}}
+                                    ],
+                                    "Expose": null,
+                                    "Config": null
+                                },
+                                "DisableDefaultTCPCheck": false,
+                                "Meta": null
+                            },
+                            "SidecarTask": null,
+                            "Gateway": null
+                        },
+                        "Meta": {"team": "numpire"},
+                        "CanaryMeta": null,
+                        "TaggedAddresses": null,
+                        "TaskName": "",
+                        "OnUpdate": "require_healthy"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let service = &group.services.unwrap()[0];
+        assert_eq!(service.provider, ServiceProvider::Consul);
+        let check = &service.checks.as_ref().unwrap()[0];
+        assert_eq!(check.type_field, CheckType::Http);
+        assert_eq!(check.address_mode, AddressMode::Auto);
+        assert_eq!(check.check_restart.as_ref().unwrap().limit, 3);
+        let proxy = service
+            .connect
+            .as_ref()
+            .unwrap()
+            .sidecar_service
+            .as_ref()
+            .unwrap()
+            .proxy
+            .as_ref()
+            .unwrap();
+        assert_eq!(proxy.upstreams.as_ref().unwrap()[0].local_bind_port, 5432);
+    }
+
+    #[test]
+    fn build_nomad_service() {
+        let service = Service {
+            provider: ServiceProvider::Nomad,
+            checks: Some(vec![ServiceCheck::tcp(
+                Duration::from_secs(5),
+                Duration::from_secs(1),
+            )]),
+            ..Service::new("numpire-worker", "metrics")
+        };
+        let value = serde_json::to_value(&service).unwrap();
+        assert_eq!(value["Provider"], "nomad");
+        assert_eq!(value["PortLabel"], "metrics");
+        assert_eq!(value["Checks"][0]["Type"], "tcp");
+        assert_eq!(value["Checks"][0]["Interval"], 5_000_000_000i64);
+        let back: Service = serde_json::from_value(value).unwrap();
+        assert_eq!(back, service);
+    }
+
+    #[test]
+    fn unknown_provider_and_check_type_are_kept() {
+        let service: Service = serde_json::from_str(
+            r#"{
+                "Name": "numpire-worker",
+                "PortLabel": "http",
+                "Provider": "catalog-v2",
+                "AddressMode": "auto",
+                "Checks": [
+                    {"Name": "ready", "Type": "h2ping", "Interval": 10000000000, "Timeout": 2000000000, "AddressMode": "bridge"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            service.provider,
+            ServiceProvider::Other("catalog-v2".to_string())
+        );
+        let check = &service.checks.as_ref().unwrap()[0];
+        assert_eq!(check.type_field, CheckType::Other("h2ping".to_string()));
+        assert_eq!(check.address_mode, AddressMode::Other("bridge".to_string()));
+
+        let value = serde_json::to_value(&service).unwrap();
+        assert_eq!(value["Provider"], "catalog-v2");
+        assert_eq!(value["Checks"][0]["Type"], "h2ping");
+        assert_eq!(value["Checks"][0]["AddressMode"], "bridge");
+        assert_eq!(AddressMode::from(""), AddressMode::Auto);
+    }
+}
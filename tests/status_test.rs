@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use nomad_api::evaluations::Evaluation;
+    use nomad_api::status::{AllocClientStatus, AllocDesiredStatus, EvalStatus, JobStatus};
+
+    #[test]
+    fn known_and_unknown_statuses() {
+        let statuses: Vec<AllocClientStatus> =
+            serde_json::from_str(r#"["running", "lost", "unknown", "suspended"]"#).unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                AllocClientStatus::Running,
+                AllocClientStatus::Lost,
+                AllocClientStatus::Unknown,
+                AllocClientStatus::Other("suspended".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&statuses).unwrap(),
+            r#"["running","lost","unknown","suspended"]"#
+        );
+        assert_eq!(JobStatus::from("dead").to_string(), "dead");
+    }
+
+    #[test]
+    fn terminal_statuses() {
+        assert!(AllocClientStatus::Complete.is_terminal());
+        assert!(!AllocClientStatus::Unknown.is_terminal());
+        assert!(AllocDesiredStatus::Evict.is_terminal());
+        assert!(!JobStatus::Other("archived".to_string()).is_terminal());
+
+        let eval: Evaluation = serde_json::from_str(
+            r#"{"ID":"c2e5b4a1","Priority":50,"Type":"batch","TriggeredBy":"job-register","Namespace":"numpire","JobID":"numpire-worker-gpu","Status":"blocked","CreateIndex":3010,"ModifyIndex":3010}"#,
+        )
+        .unwrap();
+        assert_eq!(eval.status, EvalStatus::Blocked);
+        assert!(!eval.status.is_terminal());
+    }
+}
@@ -0,0 +1,74 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockNomad, MockResponse};
+    use futures::StreamExt;
+    use nomad_api::client::NomadClient;
+    use nomad_api::error::NomadError;
+    use nomad_api::query::QueryOptions;
+
+    fn allocations(index: u64, count: usize) -> MockResponse {
+        let stubs = vec![
+            r#"{"ID":"a","EvalID":"e","Name":"n","Namespace":"default","NodeID":"","NodeName":"","JobID":"j","JobType":"batch","JobVersion":0,"TaskGroup":"g","AllocatedResources":null,"DesiredStatus":"run","DesiredDescription":"","ClientStatus":"pending","ClientDescription":null,"DesiredTransition":{},"TaskStates":null,"DeploymentStatus":null,"FollowupEvalID":null,"RescheduleTracker":null,"PreemptedAllocations":null,"PreemptedByAllocation":"","CreateIndex":1,"ModifyIndex":1,"CreateTime":0,"ModifyTime":0}"#;
+            count
+        ];
+        MockResponse::json(200, &format!("[{}]", stubs.join(",")))
+            .header("X-Nomad-Index", &index.to_string())
+    }
+
+    #[tokio::test]
+    async fn yields_on_index_change_and_resets() {
+        let server = MockNomad::start(vec![
+            allocations(5, 0),
+            allocations(5, 0),
+            allocations(7, 1),
+            allocations(3, 2),
+            allocations(3, 2),
+            MockResponse::json(404, "job not found"),
+        ]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut watcher =
+            client.watch_job_allocations("numpire-worker-gpu", &QueryOptions::default());
+
+        assert_eq!(watcher.next().await.unwrap().unwrap().len(), 0);
+        assert_eq!(watcher.next().await.unwrap().unwrap().len(), 1);
+        assert_eq!(watcher.next().await.unwrap().unwrap().len(), 2);
+        assert!(matches!(
+            watcher.next().await,
+            Some(Err(NomadError::NotFound { .. }))
+        ));
+        assert!(watcher.next().await.is_none());
+
+        let paths: Vec<String> = (0..6).map(|_| server.request().path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/v1/job/numpire-worker-gpu/allocations",
+                "/v1/job/numpire-worker-gpu/allocations?index=5",
+                "/v1/job/numpire-worker-gpu/allocations?index=5",
+                "/v1/job/numpire-worker-gpu/allocations?index=7",
+                "/v1/job/numpire-worker-gpu/allocations",
+                "/v1/job/numpire-worker-gpu/allocations?index=3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn answer_without_index_ends_the_watch() {
+        let server = MockNomad::start(vec![MockResponse::json(200, "[]")]);
+        let client = NomadClient::new(server.url.clone(), "".to_string()).unwrap();
+        let mut watcher =
+            client.watch_job_allocations("numpire-worker-gpu", &QueryOptions::default());
+
+        assert!(matches!(
+            watcher.next().await,
+            Some(Err(NomadError::InvalidRequest(_)))
+        ));
+        assert!(watcher.next().await.is_none());
+        assert_eq!(
+            server.request().path,
+            "/v1/job/numpire-worker-gpu/allocations"
+        );
+    }
+}